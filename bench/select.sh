@@ -0,0 +1,53 @@
+#!/usr/bin/env bash
+# times a few programs over a generated document of N items (default 500000).
+# usage: bench/select.sh [OTHER_JK]
+# with OTHER_JK, each program is also timed with that binary, e.g. one built
+# from another revision, so the two can be compared.
+set -e
+
+cd "$(dirname "$0")/.."
+if ! cargo build --release -q 2> /dev/null; then
+    cargo build --release
+    exit 1
+fi
+
+N=${N:-500000}
+INPUT=target/bench-$N.json
+OTHER=$1
+
+if [ ! -f "$INPUT" ]; then
+    awk -v n="$N" 'BEGIN {
+        printf "{\"meta\":{\"n\":%d},\"items\":[", n
+        for (i = 0; i < n; i++) {
+            if (i) printf ","
+            printf "{\"id\":%d,\"name\":\"item%d\",\"tags\":[\"a\",\"b\"],\"v\":%.1f}", i, i, i / 2
+        }
+        print "]}"
+    }' > "$INPUT"
+fi
+
+PROGRAMS=(
+    ".items.[-1] @ p .name"
+    ".items % p .id"
+    ".items % p _v"
+    ".items->name % p _v"
+    ".items.[?(.v < 100)] % p .name"
+    "..name @ p .[0]"
+)
+
+TIMEFORMAT=%R
+
+run() {
+    local jk=$1 program=$2
+    { time "$jk" "$program" < "$INPUT" > /dev/null; } 2>&1
+}
+
+printf "%-36s %10s" "program ($N items)" "jk"
+[ -n "$OTHER" ] && printf " %10s" "other"
+printf "\n"
+
+for program in "${PROGRAMS[@]}"; do
+    printf "%-36s %9ss" "$program" "$(run target/release/jk "$program")"
+    [ -n "$OTHER" ] && printf " %9ss" "$(run "$OTHER" "$program")"
+    printf "\n"
+done
@@ -0,0 +1,262 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use json;
+use json::Value;
+
+use unicode_width::UnicodeWidthStr;
+
+use script::kind_of;
+
+
+// the largest width or precision of a conversion
+const MAX_WIDTH: usize = 65535;
+
+// a conversion of a format, e.g. %-8.2f
+struct Spec {
+    left: bool,
+    zero: bool,
+    // '+' or ' ', put before non-negative numbers
+    sign: Option<char>,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+// formats `args` as printf does with `format`. conversions are %s for any
+// value, strings as they are; %d and %i for integers, %f and %e for
+// numbers; %x, %X and %o for integers in hex and octal; %j for any value as
+// json; and %% for a percent sign. numbers with a fraction are truncated
+// where an integer is expected.
+pub fn sprintf(format: &str, args: &[&Value]) -> Result<String, String>
+{
+    let mut result = String::new();
+    let mut chars = format.chars().peekable();
+    let mut args = args.iter();
+    let mut count = 0;
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+
+        let mut spec = Spec { left: false, zero: false, sign: None, width: 0, precision: None, conversion: '%' };
+
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                '-' => spec.left = true,
+                '0' => spec.zero = true,
+                '+' => spec.sign = Some('+'),
+                ' ' => spec.sign = spec.sign.or(Some(' ')),
+                _ => break,
+            }
+            chars.next();
+        }
+
+        spec.width = try!(digits(&mut chars, "width"));
+
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            spec.precision = Some(try!(digits(&mut chars, "precision")));
+        }
+
+        spec.conversion = match chars.next() {
+            Some(c) => c,
+            None => return Err(String::from("incomplete conversion at the end of the format")),
+        };
+
+        if spec.conversion == '%' {
+            result.push('%');
+            continue;
+        }
+
+        count += 1;
+        let arg = match args.next() {
+            Some(arg) => arg,
+            None => return Err(format!("conversion %{} has no argument", spec.conversion)),
+        };
+
+        result.push_str(&try!(convert(&spec, arg)));
+    }
+
+    let extra = args.count();
+    if extra > 0 {
+        let plural = if count == 1 { "" } else { "s" };
+        return Err(format!("format takes {} argument{}, got {}", count, plural, count + extra));
+    }
+
+    return Ok(result);
+}
+
+// the number given by the digits that follow, as the width or precision of
+// a conversion, which is capped so that a typo cannot exhaust memory
+fn digits(chars: &mut Peekable<Chars>, what: &str) -> Result<usize, String>
+{
+    let mut n: usize = 0;
+
+    while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+        n = match n.checked_mul(10).and_then(|n| n.checked_add(d as usize)) {
+            Some(n) if n <= MAX_WIDTH => n,
+            _ => return Err(format!("{} out of range", what)),
+        };
+        chars.next();
+    }
+
+    return Ok(n);
+}
+
+fn convert(spec: &Spec, arg: &Value) -> Result<String, String>
+{
+    match spec.conversion {
+        's' => {
+            let text = match arg {
+                &Value::String(ref s) => s.clone(),
+                _ => json::to_string(arg).unwrap(),
+            };
+            let text = match spec.precision {
+                Some(precision) => text.chars().take(precision).collect(),
+                None => text,
+            };
+            Ok(pad(spec, String::new(), text, false))
+        },
+        'j' => Ok(pad(spec, String::new(), json::to_string(arg).unwrap(), false)),
+        'd' | 'i' | 'x' | 'X' | 'o' => {
+            let (negative, n) = try!(integer(spec, arg));
+            let mut digits = match spec.conversion {
+                'x' => format!("{:x}", n),
+                'X' => format!("{:X}", n),
+                'o' => format!("{:o}", n),
+                _ => n.to_string(),
+            };
+            if let Some(precision) = spec.precision {
+                while digits.len() < precision {
+                    digits.insert(0, '0');
+                }
+            }
+            Ok(pad(spec, sign(spec, negative), digits, true))
+        },
+        'f' | 'e' => {
+            let f = try!(arg.as_f64().ok_or_else(|| type_error(spec, arg)));
+            let precision = spec.precision.unwrap_or(6);
+            let digits = match spec.conversion {
+                'f' => format!("{:.*}", precision, f.abs()),
+                _ => exponent(f.abs(), precision),
+            };
+            Ok(pad(spec, sign(spec, f.is_sign_negative() && f != 0.0), digits, true))
+        },
+        c => Err(format!("unknown conversion %{}", c)),
+    }
+}
+
+fn type_error(spec: &Spec, arg: &Value) -> String
+{
+    return format!("%{} expects a number, got {}", spec.conversion, kind_of(arg));
+}
+
+// the sign and magnitude of an integer argument
+fn integer(spec: &Spec, arg: &Value) -> Result<(bool, u64), String>
+{
+    if let Some(u) = arg.as_u64() {
+        return Ok((false, u));
+    }
+
+    if let Some(i) = arg.as_i64() {
+        return Ok((i < 0, i.unsigned_abs()));
+    }
+
+    match arg.as_f64() {
+        Some(f) if f.abs() < 18446744073709551616.0 => Ok((f <= -1.0, f.abs().trunc() as u64)),
+        Some(f) => Err(format!("%{} argument {} is out of range", spec.conversion, f)),
+        None => Err(type_error(spec, arg)),
+    }
+}
+
+fn sign(spec: &Spec, negative: bool) -> String
+{
+    if negative {
+        return String::from("-");
+    }
+
+    return spec.sign.map_or(String::new(), |c| c.to_string());
+}
+
+// `f` as in C's %e, e.g. 1.500000e+03
+fn exponent(f: f64, precision: usize) -> String
+{
+    let text = format!("{:.*e}", precision, f);
+    let (mantissa, exp) = text.split_at(text.find('e').unwrap());
+    let exp: i32 = exp[1..].parse().unwrap();
+
+    return format!("{}e{}{:02}", mantissa, if exp < 0 { '-' } else { '+' }, exp.abs());
+}
+
+// `prefix` and `text` padded to the width of the spec. numbers may be padded
+// with zeros, which go between the sign and the digits.
+fn pad(spec: &Spec, prefix: String, text: String, number: bool) -> String
+{
+    let width = UnicodeWidthStr::width(prefix.as_str()) + UnicodeWidthStr::width(text.as_str());
+
+    if width >= spec.width {
+        return prefix + &text;
+    }
+
+    let padding = spec.width - width;
+
+    if spec.left {
+        return prefix + &text + &" ".repeat(padding);
+    }
+
+    if spec.zero && number {
+        return prefix + &"0".repeat(padding) + &text;
+    }
+
+    return " ".repeat(padding) + &prefix + &text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(format: &str, args: &str) -> Result<String, String> {
+        let args: Vec<Value> = json::from_str(args).unwrap();
+        let args: Vec<&Value> = args.iter().collect();
+        sprintf(format, &args)
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(format("%5d|%-5d|%05d|%+d|% d|%+ d", "[42, 42, 42, 42, 42, 42]").unwrap(), "   42|42   |00042|+42| 42|+42");
+        assert_eq!(format("%05d|%-05d|%+d", "[-42, -42, -42]").unwrap(), "-0042|-42  |-42");
+        assert_eq!(format("%x %X %o %08.3f", "[255, 255, 255, -3.14159]").unwrap(), "ff FF 377 -003.142");
+        assert_eq!(format("100%%", "[]").unwrap(), "100%");
+    }
+
+    #[test]
+    fn width_and_precision() {
+        assert_eq!(format("[%6s][%-6s][%.2s][%6.2s]", r#"["abc", "abc", "abc", "abc"]"#).unwrap(), "[   abc][abc   ][ab][    ab]");
+        assert_eq!(format("%.3f %.0f %e %.2e", "[3.14159, 2.5, 1500, 0.000123]").unwrap(), "3.142 2 1.500000e+03 1.23e-04");
+        assert_eq!(format("%.4d %6.3d %d", "[7, -7, 2.9]").unwrap(), "0007   -007 2");
+        assert_eq!(format("%4s|%-4j|", r#"["é", "é"]"#).unwrap(), "   é|\"é\" |");
+        assert_eq!(format("%65535d", "[1]").unwrap().len(), 65535);
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(format("%65536d", "[1]"), Err(String::from("width out of range")));
+        assert_eq!(format("%999999999999d", "[1]"), Err(String::from("width out of range")));
+        assert_eq!(format("%99999999999999999999999999d", "[1]"), Err(String::from("width out of range")));
+        assert_eq!(format("%.999999999999f", "[1]"), Err(String::from("precision out of range")));
+        assert_eq!(format("%d", "[1e30]"), Err(String::from("%d argument 1000000000000000000000000000000 is out of range")));
+    }
+
+    #[test]
+    fn arguments() {
+        assert_eq!(format("%s and %s", r#"["a"]"#), Err(String::from("conversion %s has no argument")));
+        assert_eq!(format("%s", r#"["a", "b"]"#), Err(String::from("format takes 1 argument, got 2")));
+        assert_eq!(format("%%", r#"["a", "b"]"#), Err(String::from("format takes 0 arguments, got 2")));
+        assert_eq!(format("%d", r#"["a"]"#), Err(String::from("%d expects a number, got a string")));
+        assert_eq!(format("%q", "[1]"), Err(String::from("unknown conversion %q")));
+        assert_eq!(format("a %-5", "[1]"), Err(String::from("incomplete conversion at the end of the format")));
+    }
+}
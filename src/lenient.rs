@@ -0,0 +1,550 @@
+use std::f64;
+use std::i64;
+use std::char;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use json::Value;
+use json::Map;
+
+use script::{JkError, syntax_error};
+
+
+// parses json5, a superset of json for hand-written files such as configs:
+// comments, trailing commas, single-quoted strings, unquoted keys, hex
+// numbers, leading or trailing decimal points, Infinity and NaN. values come
+// out as they would from the json parser; Infinity and NaN, which json has no
+// numbers for, become null.
+pub struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    // objects and arrays open around the next value
+    depth: usize,
+}
+
+// objects and arrays may nest less deeply than this, as in the json parser,
+// so that parsing them recursively does not overflow the stack
+const MAX_DEPTH: usize = 128;
+
+pub fn from_str(input: &str) -> Result<Value, JkError>
+{
+    let mut parser = Parser::new(input);
+    let value = try!(parser.parse_value());
+
+    if try!(parser.skip_ws()).is_some() {
+        return parser.error("trailing characters");
+    }
+
+    return Ok(value);
+}
+
+fn is_ident_start(c: char) -> bool
+{
+    c == '$' || c == '_' || c == '\\' || c.is_alphabetic()
+}
+
+fn is_ident_part(c: char) -> bool
+{
+    is_ident_start(c) || c.is_alphanumeric() || c == '\u{200c}' || c == '\u{200d}'
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Parser<'a>
+    {
+        Parser {
+            chars: input.chars().peekable(),
+            line: 1,
+            column: 0,
+            depth: 0,
+        }
+    }
+
+    // an error at the next character
+    fn error<T>(&self, msg: &str) -> Result<T, JkError>
+    {
+        return Err(syntax_error(String::from(msg), self.line, self.column + 1));
+    }
+
+    fn peek(&mut self) -> Option<char>
+    {
+        self.chars.peek().cloned()
+    }
+
+    fn bump(&mut self) -> Option<char>
+    {
+        let c = self.chars.next();
+
+        if c == Some('\n') {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+
+        return c;
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JkError>
+    {
+        if try!(self.skip_ws()) == Some(expected) {
+            self.bump();
+            return Ok(());
+        }
+
+        return self.error(&format!("expected '{}'", expected));
+    }
+
+    // skips whitespace and comments, and peeks at the character that follows
+    fn skip_ws(&mut self) -> Result<Option<char>, JkError>
+    {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || c == '\u{feff}' => { self.bump(); },
+                Some('/') => try!(self.skip_comment()),
+                c => return Ok(c),
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) -> Result<(), JkError>
+    {
+        self.bump();
+
+        match self.bump() {
+            Some('/') => {
+                while self.peek().is_some() && self.peek() != Some('\n') {
+                    self.bump();
+                }
+            },
+            Some('*') => {
+                loop {
+                    match self.bump() {
+                        None => return self.error("EOF while parsing a comment"),
+                        Some('*') if self.peek() == Some('/') => {
+                            self.bump();
+                            break;
+                        },
+                        Some(_) => {},
+                    }
+                }
+            },
+            _ => return self.error("expected '//' or '/*' comment"),
+        }
+
+        return Ok(());
+    }
+
+    fn parse_value(&mut self) -> Result<Value, JkError>
+    {
+        match try!(self.skip_ws()) {
+            None => self.error("EOF while parsing a value"),
+            Some(c) if c == '{' || c == '[' => {
+                if self.depth + 1 == MAX_DEPTH {
+                    return self.error("recursion limit exceeded");
+                }
+
+                self.depth += 1;
+                let value = if c == '{' { self.parse_object() } else { self.parse_array() };
+                self.depth -= 1;
+                value
+            },
+            Some(q) if q == '"' || q == '\'' => self.parse_string(q).map(Value::String),
+            Some(c) if c == '-' || c == '+' || c == '.' || c.is_digit(10) => self.parse_number(),
+            Some(c) if is_ident_start(c) => {
+                // an unknown word is reported where it starts
+                let (line, column) = (self.line, self.column + 1);
+                let word = try!(self.parse_identifier());
+                match word.as_str() {
+                    "null" => Ok(Value::Null),
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "Infinity" => Ok(Value::from(f64::INFINITY)),
+                    "NaN" => Ok(Value::from(f64::NAN)),
+                    _ => Err(syntax_error(format!("unexpected identifier '{}'", word), line, column)),
+                }
+            },
+            Some(c) => self.error(&format!("unexpected character '{}'", c)),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, JkError>
+    {
+        let mut object = Map::new();
+        self.bump();
+
+        loop {
+            let key = match try!(self.skip_ws()) {
+                Some('}') => break,
+                Some(q) if q == '"' || q == '\'' => try!(self.parse_string(q)),
+                Some(c) if is_ident_start(c) => try!(self.parse_identifier()),
+                _ => return self.error("expected an object key or '}'"),
+            };
+
+            try!(self.expect(':'));
+            let value = try!(self.parse_value());
+            object.insert(key, value);
+
+            match try!(self.skip_ws()) {
+                Some(',') => { self.bump(); },
+                Some('}') => break,
+                _ => return self.error("expected ',' or '}'"),
+            }
+        }
+
+        self.bump();
+        return Ok(Value::Object(object));
+    }
+
+    fn parse_array(&mut self) -> Result<Value, JkError>
+    {
+        let mut vector = Vec::new();
+        self.bump();
+
+        loop {
+            if try!(self.skip_ws()) == Some(']') {
+                break;
+            }
+
+            vector.push(try!(self.parse_value()));
+
+            match try!(self.skip_ws()) {
+                Some(',') => { self.bump(); },
+                Some(']') => break,
+                _ => return self.error("expected ',' or ']'"),
+            }
+        }
+
+        self.bump();
+        return Ok(Value::Array(vector));
+    }
+
+    fn parse_identifier(&mut self) -> Result<String, JkError>
+    {
+        let mut ident = String::new();
+
+        while let Some(c) = self.peek() {
+            if !is_ident_part(c) {
+                break;
+            }
+
+            self.bump();
+
+            if c == '\\' {
+                if self.bump() != Some('u') {
+                    return self.error("expected a \\u escape in identifier");
+                }
+                ident.push(try!(self.parse_unicode_escape()));
+            } else {
+                ident.push(c);
+            }
+        }
+
+        return Ok(ident);
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, JkError>
+    {
+        let mut s = String::new();
+        self.bump();
+
+        loop {
+            match self.bump() {
+                None => return self.error("EOF while parsing a string"),
+                Some(c) if c == quote => return Ok(s),
+                Some('\\') => {
+                    if let Some(c) = try!(self.parse_escape()) {
+                        s.push(c);
+                    }
+                },
+                Some('\n') | Some('\r') => return self.error("line break in a string"),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    // the character an escape stands for, or None for an escaped line break,
+    // which continues the string on the next line
+    fn parse_escape(&mut self) -> Result<Option<char>, JkError>
+    {
+        let c = match self.bump() {
+            None => return self.error("EOF while parsing a string"),
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\u{b}',
+            Some('0') if !self.peek().map_or(false, |c| c.is_digit(10)) => '\0',
+            Some('x') => {
+                let code = try!(self.parse_hex(2));
+                char::from_u32(code).unwrap()
+            },
+            Some('u') => try!(self.parse_unicode_escape()),
+            Some('\r') => {
+                if self.peek() == Some('\n') {
+                    self.bump();
+                }
+                return Ok(None);
+            },
+            Some('\n') | Some('\u{2028}') | Some('\u{2029}') => return Ok(None),
+            Some(c) if c.is_digit(10) => return self.error("invalid escape"),
+            Some(c) => c,
+        };
+
+        return Ok(Some(c));
+    }
+
+    fn parse_hex(&mut self, digits: usize) -> Result<u32, JkError>
+    {
+        let mut code = 0;
+
+        for _ in 0..digits {
+            match self.bump().and_then(|c| c.to_digit(16)) {
+                Some(d) => code = code * 16 + d,
+                None => return self.error("invalid hex escape"),
+            }
+        }
+
+        return Ok(code);
+    }
+
+    // the four hex digits after '\u', with the low half of a surrogate pair
+    // following a high half
+    fn parse_unicode_escape(&mut self) -> Result<char, JkError>
+    {
+        let code = try!(self.parse_hex(4));
+
+        if code >= 0xd800 && code < 0xdc00 {
+            if self.bump() != Some('\\') || self.bump() != Some('u') {
+                return self.error("lone leading surrogate in hex escape");
+            }
+
+            let low = try!(self.parse_hex(4));
+            if low < 0xdc00 || low >= 0xe000 {
+                return self.error("lone leading surrogate in hex escape");
+            }
+
+            let code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+            return Ok(char::from_u32(code).unwrap());
+        }
+
+        match char::from_u32(code) {
+            Some(c) => Ok(c),
+            None => self.error("lone trailing surrogate in hex escape"),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, JkError>
+    {
+        let negative = match self.peek() {
+            Some('-') => { self.bump(); true },
+            Some('+') => { self.bump(); false },
+            _ => false,
+        };
+
+        if self.peek().map_or(false, is_ident_start) {
+            let sign = if negative { -1.0 } else { 1.0 };
+            return match try!(self.parse_identifier()).as_str() {
+                "Infinity" => Ok(Value::from(sign * f64::INFINITY)),
+                "NaN" => Ok(Value::from(f64::NAN)),
+                _ => self.error("invalid number"),
+            };
+        }
+
+        let mut text = String::from(if negative { "-" } else { "" });
+        let int_digits = self.take_digits(&mut text);
+
+        if int_digits == "0" && (self.peek() == Some('x') || self.peek() == Some('X')) {
+            self.bump();
+            return self.parse_hex_number(negative);
+        }
+
+        if int_digits.len() > 1 && int_digits.starts_with("0") {
+            return self.error("invalid number");
+        }
+
+        let mut float = false;
+
+        if self.peek() == Some('.') {
+            self.bump();
+            text.push('.');
+            float = true;
+
+            if self.take_digits(&mut text).is_empty() && int_digits.is_empty() {
+                return self.error("invalid number");
+            }
+        } else if int_digits.is_empty() {
+            return self.error("invalid number");
+        }
+
+        if self.peek() == Some('e') || self.peek() == Some('E') {
+            self.bump();
+            text.push('e');
+            float = true;
+
+            if let Some(sign) = self.peek().filter(|&c| c == '+' || c == '-') {
+                self.bump();
+                text.push(sign);
+            }
+
+            if self.take_digits(&mut text).is_empty() {
+                return self.error("invalid number");
+            }
+        }
+
+        // integers are kept as integers as long as they fit, like the json
+        // parser does
+        if !float {
+            if negative {
+                if let Ok(i) = text.parse::<i64>() {
+                    return Ok(Value::from(i));
+                }
+            } else if let Ok(u) = text.parse::<u64>() {
+                return Ok(Value::from(u));
+            }
+        }
+
+        match text.parse::<f64>() {
+            Ok(f) => Ok(Value::from(f)),
+            Err(_) => self.error("invalid number"),
+        }
+    }
+
+    // appends the decimal digits that follow to `text`, and returns them
+    fn take_digits(&mut self, text: &mut String) -> String
+    {
+        let mut digits = String::new();
+
+        while let Some(c) = self.peek().filter(|c| c.is_digit(10)) {
+            self.bump();
+            digits.push(c);
+        }
+
+        text.push_str(&digits);
+        return digits;
+    }
+
+    fn parse_hex_number(&mut self, negative: bool) -> Result<Value, JkError>
+    {
+        let mut n: u64 = 0;
+        let mut empty = true;
+
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(16)) {
+            self.bump();
+            empty = false;
+
+            n = match n.checked_mul(16).and_then(|n| n.checked_add(d as u64)) {
+                Some(n) => n,
+                None => return self.error("hex number out of range"),
+            };
+        }
+
+        if empty {
+            return self.error("invalid hex number");
+        }
+
+        if !negative {
+            return Ok(Value::from(n));
+        }
+
+        if n > i64::MAX as u64 + 1 {
+            return self.error("hex number out of range");
+        }
+
+        return Ok(Value::from((n as i64).wrapping_neg()));
+    }
+}
+
+// a stream of top-level values, as in json lines
+impl<'a> Iterator for Parser<'a> {
+    type Item = Result<Value, JkError>;
+
+    fn next(&mut self) -> Option<Result<Value, JkError>>
+    {
+        match self.skip_ws() {
+            Ok(None) => None,
+            Ok(Some(_)) => Some(self.parse_value()),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use json;
+
+    fn parse(text: &str) -> Value {
+        from_str(text).unwrap()
+    }
+
+    fn value(text: &str) -> Value {
+        json::from_str(text).unwrap()
+    }
+
+    fn error(text: &str) -> String {
+        match from_str(text).unwrap_err() {
+            JkError::Parse(e) => e.msg,
+            e => panic!("unexpected error {}", e),
+        }
+    }
+
+    fn position(text: &str) -> (usize, usize) {
+        match from_str(text).unwrap_err() {
+            JkError::Parse(e) => (e.line, e.column),
+            e => panic!("unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    fn comments() {
+        let text = "// a config\n{\n  a: 1, // the first\n  /* b: 2, */ c: /* inline */ 3\n}\n// done";
+        assert_eq!(parse(text), value(r#"{"a": 1, "c": 3}"#));
+        assert_eq!(error("[1 /* open"), "EOF while parsing a comment");
+        assert_eq!(error("[1 / 2]"), "expected '//' or '/*' comment");
+    }
+
+    #[test]
+    fn trailing_commas() {
+        assert_eq!(parse("[1, 2,]"), value("[1, 2]"));
+        assert_eq!(parse("{a: [], 'b': {},}"), value(r#"{"a": [], "b": {}}"#));
+        assert_eq!(error("[1,,]"), "unexpected character ','");
+        assert_eq!(error("{,}"), "expected an object key or '}'");
+    }
+
+    #[test]
+    fn hex_numbers() {
+        assert_eq!(parse("[0xff, 0X1A, -0x10, +0x0]"), value("[255, 26, -16, 0]"));
+        assert_eq!(parse("0xffffffffffffffff"), value("18446744073709551615"));
+        assert_eq!(parse("-0x8000000000000000"), value("-9223372036854775808"));
+        assert_eq!(error("0x10000000000000000"), "hex number out of range");
+        assert_eq!(error("-0x8000000000000001"), "hex number out of range");
+        assert_eq!(error("0x"), "invalid hex number");
+    }
+
+    #[test]
+    fn surrogate_escapes() {
+        assert_eq!(parse(r#"'\ud83d\ude00'"#), value(r#""\ud83d\ude00""#));
+        assert_eq!(parse(r#"{a\u0062: "\u00e9"}"#), value(r#"{"ab": "\u00e9"}"#));
+        assert_eq!(error(r#"'\ud83d'"#), "lone leading surrogate in hex escape");
+        assert_eq!(error(r#"'\ud83dA'"#), "lone leading surrogate in hex escape");
+        assert_eq!(error(r#"'\ude00'"#), "lone trailing surrogate in hex escape");
+    }
+
+    #[test]
+    fn unknown_words() {
+        assert_eq!(error("[1, tru]"), "unexpected identifier 'tru'");
+        assert_eq!(position("[1, tru]"), (1, 5));
+        assert_eq!(position("{a: 1,\n  b: nul}"), (2, 6));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(parse(&nested(127)), value(&nested(127)));
+        assert_eq!(error(&nested(128)), "recursion limit exceeded");
+        assert_eq!(error(&"{a: [".repeat(5000)), "recursion limit exceeded");
+    }
+}
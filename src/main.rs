@@ -2,24 +2,44 @@ extern crate serde_json as json;
 
 #[macro_use]
 extern crate lazy_static;
+extern crate indexmap;
+extern crate yaml_rust2;
+extern crate unicode_width;
 
 use std::env;
+use std::process;
 use std::io;
+use std::io::BufRead;
+use std::fs::File;
 use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::iter::Iterator;
+use std::fmt;
 
 mod script;
 mod parse;
+mod options;
+mod stream;
+mod lenient;
+mod yaml;
+mod syntax;
+mod output;
+mod format;
 
-use parse::script;
+use parse::{script, jsonpath_script};
 use script::*;
+use options::{Options, InputFormat, parse_options};
+use stream::{Scanner, RecentLines};
+use output::Output;
 
 
 use std::string::String;
 use std::str;
 
 use json::Value;
-use json::Map;
+
+use indexmap::IndexMap;
 
 
 lazy_static! {
@@ -33,240 +53,1301 @@ fn action_error(msg: &str) -> Result<(), JkError>
 }
 
 
-fn value_error(msg: &str) -> Result<Value, JkError>
+fn value_error<'a>(msg: &str) -> Result<Cow<'a, Value>, JkError>
 {
     return Err(JkError::Action(String::from(msg)));
 }
 
-fn sop_json_value(value: Value, sop: &Sop) -> Result<Value, JkError>
+// position of `index` in an array of length `len`; negative indexes count
+// from the end
+fn resolve_index(index: i64, len: usize) -> Option<usize>
+{
+    let i = if index < 0 { index + len as i64 } else { index };
+
+    if i < 0 || i >= len as i64 {
+        return None;
+    }
+
+    return Some(i as usize);
+}
+
+fn resolve_path<'a>(value: &'a Value, path: &Vec<PathStep>) -> Option<&'a Value>
+{
+    let mut curr = value;
+
+    for step in path {
+        let next = match (curr, step) {
+            (&Value::Object(ref object), &PathStep::Key(ref key)) => object.get(key),
+            (&Value::Array(ref vector), &PathStep::Index(index)) => {
+                resolve_index(index, vector.len()).map(|i| &vector[i])
+            },
+            _ => None,
+        };
+
+        match next {
+            Some(v) => curr = v,
+            None => return None,
+        }
+    }
+
+    return Some(curr);
+}
+
+// numbers compare by value whatever their representation, strings compare
+// lexically; any other pair only supports (in)equality
+fn compare_values(op: CmpOp, left: &Value, right: &Value) -> bool
+{
+    let ordering = match (left.as_f64(), right.as_f64()) {
+        (Some(l), Some(r)) => l.partial_cmp(&r),
+        _ => match (left, right) {
+            (&Value::String(ref l), &Value::String(ref r)) => Some(l.cmp(r)),
+            _ => if left == right { Some(Ordering::Equal) } else { None },
+        },
+    };
+
+    match (op, ordering) {
+        (CmpOp::Eq, Some(o)) => o == Ordering::Equal,
+        (CmpOp::Ne, Some(o)) => o != Ordering::Equal,
+        (CmpOp::Ne, None) => true,
+        (CmpOp::Lt, Some(o)) => o == Ordering::Less,
+        (CmpOp::Le, Some(o)) => o != Ordering::Greater,
+        (CmpOp::Gt, Some(o)) => o == Ordering::Greater,
+        (CmpOp::Ge, Some(o)) => o != Ordering::Less,
+        _ => false,
+    }
+}
+
+fn filter_operand<'a>(v: &'a Value, operand: &'a FilterOperand) -> Option<&'a Value>
+{
+    match operand {
+        &FilterOperand::Path(ref path) => resolve_path(v, path),
+        &FilterOperand::Literal(ref literal) => Some(literal),
+    }
+}
+
+// a path that does not resolve only equals another missing path
+fn filter_matches(v: &Value, filter: &Filter) -> bool
+{
+    match filter {
+        &Filter::Exists(ref path) => resolve_path(v, path).is_some(),
+        &Filter::Compare(op, ref left, ref right) => {
+            match (filter_operand(v, left), filter_operand(v, right)) {
+                (Some(l), Some(r)) => compare_values(op, l, r),
+                (None, None) => op == CmpOp::Eq || op == CmpOp::Le || op == CmpOp::Ge,
+                _ => op == CmpOp::Ne,
+            }
+        },
+        &Filter::And(ref left, ref right) => filter_matches(v, left) && filter_matches(v, right),
+        &Filter::Or(ref left, ref right) => filter_matches(v, left) || filter_matches(v, right),
+        &Filter::Not(ref filter) => !filter_matches(v, filter),
+        &Filter::Query(ref selectors) => query_selects(v, selectors),
+    }
+}
+
+// whether a jsonpath query from `v` selects any node
+fn query_selects(v: &Value, selectors: &Vec<Selector>) -> bool
+{
+    let mut node = Node::root(v);
+
+    for selector in selectors {
+        node = match select_json(node, selector, MissingPolicy::Skip) {
+            Ok(node) => node,
+            Err(_) => return false,
+        };
+    }
+
+    match node {
+        Node::Array(nodes) => !nodes.is_empty(),
+        _ => true,
+    }
+}
+
+fn missing_node<'a>(policy: MissingPolicy, msg: String) -> Result<Option<Node<'a>>, JkError>
+{
+    match policy {
+        MissingPolicy::Error => Err(JkError::Query(msg)),
+        MissingPolicy::Null => Ok(Some(Node::Missing)),
+        MissingPolicy::Skip => Ok(None),
+    }
+}
+
+// filters look at values; nodes built by selectors are materialized first
+fn node_matches(node: &Node, filter: &Filter) -> bool
 {
-    match value {
-        Value::Array(vector) => sop_json_array(vector, sop),
-        Value::Object(object) => sop_json_object(object, sop),
-        value @ _ => Ok(value.clone()),
+    match node {
+        &Node::Value(v, _) => filter_matches(v, filter),
+        _ => filter_matches(&node.to_value(), filter),
     }
 }
 
-fn sop_json_array(v: Vec<Value>, sop: &Sop) -> Result<Value, JkError>
+// selects from `node`, returning None when the selected element is missing
+// and the policy says to skip it
+fn sop_json_value<'a>(node: Node<'a>, sop: &Sop, policy: MissingPolicy) -> Result<Option<Node<'a>>, JkError>
+{
+    if let &Sop::Optional(ref sop) = sop {
+        return sop_json_value(node, sop, policy.optional());
+    }
+
+    // a union, only found in jsonpath queries, selects as they do
+    if let &Sop::Union(_) = sop {
+        let mut result = Vec::new();
+        sop_children(&node.into_children(), sop, &mut result);
+        return Ok(Some(Node::Array(result)));
+    }
+
+    // the wildcard keeps an object as it is, path included
+    if let &Sop::Object(ObjectIndexer::Wildcard) = sop {
+        if node.is_object() {
+            return Ok(Some(node));
+        }
+    }
+
+    match node.into_children() {
+        Children::Array(nodes) => sop_json_array(nodes, sop, policy),
+        Children::Object(nodes) => sop_json_object(nodes, sop, policy),
+        // what is already missing stays so, whatever selects from it
+        Children::Scalar(Node::Missing) => Ok(Some(Node::Missing)),
+        // a filter keeps a scalar that matches it; one that does not is
+        // missing, though never an error, as a filter selecting nothing from
+        // an array is not one
+        Children::Scalar(node) => {
+            match sop {
+                &Sop::Filter(ref filter) if node_matches(&node, filter) => Ok(Some(node)),
+                &Sop::Filter(_) => missing_node(policy.optional(), format!("{} does not match {}", kind_of(&node.to_value()), sop)),
+                sop => missing_node(policy, format!("selector {} applied to {}", sop, kind_of(&node.to_value()))),
+            }
+        },
+    }
+}
+
+// array index of a json pointer token: digits without leading zeros. '-'
+// refers to the element after the last one, which never exists.
+fn pointer_index(token: &str) -> Option<usize>
+{
+    if token.is_empty() || (token.len() > 1 && token.starts_with("0")) {
+        return None;
+    }
+
+    if !token.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    return token.parse().ok();
+}
+
+fn sop_json_array<'a>(v: Vec<Node<'a>>, sop: &Sop, policy: MissingPolicy) -> Result<Option<Node<'a>>, JkError>
 {
     match sop {
-        &Sop::ArraySlice(ref slice) => Ok(Value::Array(v[slice.to_range(v.len())].to_vec())),
-        &Sop::ArrayIndex(index) => Ok(v[index as usize].clone()),
+        &Sop::ArraySlice(ref slice) => {
+            let indices = slice.to_indices(v.len());
+            Ok(Some(Node::Array(indices.into_iter().map(|i| v[i].clone()).collect())))
+        },
+        &Sop::ArrayIndex(index) => {
+            match resolve_index(index, v.len()) {
+                Some(i) => Ok(Some(v[i].clone())),
+                None => missing_node(policy, format!("selector {} out of range for array of length {}", sop, v.len())),
+            }
+        },
+        &Sop::ArrayUnion(ref indices) => {
+            let mut result = Vec::new();
+
+            for &index in indices {
+                let selected = match resolve_index(index, v.len()) {
+                    Some(i) => Some(v[i].clone()),
+                    None => try!(missing_node(policy, format!("index {} of selector {} out of range for array of length {}", index, sop, v.len()))),
+                };
+
+                if let Some(selected) = selected {
+                    result.push(selected);
+                }
+            }
+
+            Ok(Some(Node::Array(result)))
+        },
+        
+        &Sop::Filter(ref filter) => {
+            Ok(Some(Node::Array(v.into_iter().filter(|e| node_matches(e, filter)).collect())))
+        },
+        
+        &Sop::Optional(ref sop) => sop_json_array(v, sop, policy.optional()),
+
+        &Sop::Union(_) => sop_json_value(Node::Array(v), sop, policy),
+
+        &Sop::PointerToken(ref token) => {
+            match pointer_index(token) {
+                Some(i) if i < v.len() => Ok(Some(v[i].clone())),
+                Some(_) => missing_node(policy, format!("selector {} out of range for array of length {}", sop, v.len())),
+                None => missing_node(policy, format!("selector {} is not an index into an array", sop)),
+            }
+        },
         
         // coercing to foreach
-        &Sop::Object(_) => sop_foreach_json_array(v, sop),
+        &Sop::Object(_) | &Sop::ObjectUnion(_) => sop_foreach_json_array(v, sop, policy).map(Some),
     }
 }
 
-fn sop_json_object(o: Map<String, Value>, sop: &Sop) -> Result<Value, JkError>
+fn sop_json_object<'a>(o: IndexMap<String, Node<'a>>, sop: &Sop, policy: MissingPolicy) -> Result<Option<Node<'a>>, JkError>
 {
+    if let &Sop::Filter(ref filter) = sop {
+        let matched = o.into_iter().filter(|&(_, ref v)| node_matches(v, filter)).collect();
+        return Ok(Some(Node::Object(matched)));
+    }
+
+    if let &Sop::ObjectUnion(ref keys) = sop {
+        let mut result = IndexMap::new();
+
+        for key in keys {
+            let selected = match o.get(key) {
+                Some(v) => Some(v.clone()),
+                None => try!(missing_node(policy, format!("key {} of selector {} not found in object", key, sop))),
+            };
+
+            if let Some(selected) = selected {
+                result.insert(key.clone(), selected);
+            }
+        }
+
+        return Ok(Some(Node::Object(result)));
+    }
+
+    if let &Sop::PointerToken(ref key) = sop {
+        return match o.get(key) {
+            Some(v) => Ok(Some(v.clone())),
+            None => missing_node(policy, format!("selector {} not found in object", sop)),
+        };
+    }
+
     if let &Sop::Object(ref indexer) = sop {
         match indexer {
-            &ObjectIndexer::Wildcard => Ok(Value::Object(o)),
-            &ObjectIndexer::Exact(ref key) => o.get(key).cloned().ok_or(JkError::Query(String::from("missing"))),
+            &ObjectIndexer::Wildcard => Ok(Some(Node::Object(o))),
+            &ObjectIndexer::Exact(ref key) => {
+                match o.get(key) {
+                    Some(v) => Ok(Some(v.clone())),
+                    None => missing_node(policy, format!("selector {} not found in object", sop)),
+                }
+            },
         }
     } else {
-        return Err(JkError::Query(String::from("bad object selector")));
+        return missing_node(policy, format!("array selector {} applied to an object", sop));
     }
 }
 
-fn sop_foreach_json_array(vector: Vec<Value>, sop: &Sop) -> Result<Value, JkError>
+fn sop_foreach_json_array<'a>(vector: Vec<Node<'a>>, sop: &Sop, policy: MissingPolicy) -> Result<Node<'a>, JkError>
 {
     let mut result = Vec::new();
     
     for v in vector {
-        result.push(try!(sop_json_value(v, sop)));
+        if let Some(selected) = try!(sop_json_value(v, sop, policy)) {
+            result.push(selected);
+        }
     }
     
-    return Ok(Value::Array(result));
+    return Ok(Node::Array(result));
 }
 
-fn sop_foreach_json_object(object: Map<String, Value>, sop: &Sop) -> Result<Value, JkError>
+fn sop_foreach_json_object<'a>(object: IndexMap<String, Node<'a>>, sop: &Sop, policy: MissingPolicy) -> Result<Node<'a>, JkError>
 {
     let mut result = Vec::new();
     
     for (_, v) in object {
-        result.push(try!(sop_json_value(v, sop)));
+        if let Some(selected) = try!(sop_json_value(v, sop, policy)) {
+            result.push(selected);
+        }
     }
     
-    return Ok(Value::Array(result));
+    return Ok(Node::Array(result));
+}
+
+// collects what `sop` selects among `children`, ignoring anything missing
+// or of the wrong type
+fn sop_children<'a>(children: &Children<'a>, sop: &Sop, result: &mut Vec<Node<'a>>)
+{
+    match (children, sop) {
+        (_, &Sop::Optional(ref sop)) => sop_children(children, sop, result),
+        (_, &Sop::Union(ref sops)) => {
+            for sop in sops {
+                sop_children(children, sop, result);
+            }
+        },
+        (&Children::Object(ref object), &Sop::Object(ObjectIndexer::Exact(ref key))) => {
+            result.extend(object.get(key).cloned());
+        },
+        (&Children::Object(ref object), &Sop::Object(ObjectIndexer::Wildcard)) => {
+            result.extend(object.values().cloned());
+        },
+        (&Children::Array(ref vector), &Sop::Object(ObjectIndexer::Wildcard)) => {
+            result.extend(vector.iter().cloned());
+        },
+        (&Children::Object(ref object), &Sop::ObjectUnion(ref keys)) => {
+            result.extend(keys.iter().filter_map(|k| object.get(k)).cloned());
+        },
+        (&Children::Array(ref vector), &Sop::ArrayIndex(index)) => {
+            if let Some(i) = resolve_index(index, vector.len()) {
+                result.push(vector[i].clone());
+            }
+        },
+        (&Children::Array(ref vector), &Sop::ArrayUnion(ref indices)) => {
+            result.extend(indices.iter().filter_map(|&i| resolve_index(i, vector.len())).map(|i| vector[i].clone()));
+        },
+        (&Children::Array(ref vector), &Sop::ArraySlice(ref slice)) => {
+            result.extend(slice.to_indices(vector.len()).into_iter().map(|i| vector[i].clone()));
+        },
+        (&Children::Array(ref vector), &Sop::Filter(ref filter)) => {
+            result.extend(vector.iter().filter(|v| node_matches(v, filter)).cloned());
+        },
+        (&Children::Object(ref object), &Sop::Filter(ref filter)) => {
+            result.extend(object.values().filter(|v| node_matches(v, filter)).cloned());
+        },
+        _ => {},
+    }
+}
+
+// applies `sop` to `node` and to each of its descendants, in document order
+fn descend_json<'a>(node: Node<'a>, sop: &Sop, result: &mut Vec<Node<'a>>)
+{
+    let children = node.into_children();
+    sop_children(&children, sop, result);
+
+    match children {
+        Children::Array(vector) => {
+            for v in vector {
+                descend_json(v, sop, result);
+            }
+        },
+        Children::Object(object) => {
+            for (_, v) in object {
+                descend_json(v, sop, result);
+            }
+        },
+        Children::Scalar(_) => {},
+    }
 }
 
-fn select_json(value: Value, selector: &Selector) -> Result<Value, JkError>
+fn select_json<'a>(node: Node<'a>, selector: &Selector, policy: MissingPolicy) -> Result<Node<'a>, JkError>
 {
     match selector {
-        &Selector::ForSelf(ref sop) => sop_json_value(value, sop),
+        &Selector::ForSelf(ref sop) => {
+            let selected = try!(sop_json_value(node, sop, policy));
+            Ok(selected.unwrap_or(Node::Missing))
+        },
         &Selector::ForEach(ref sop) => {
-            match value {
-                Value::Array(vector) => sop_foreach_json_array(vector, sop),
-                Value::Object(object) => sop_foreach_json_object(object, sop),
-                _ => sop_json_value(value, sop),
+            match node.into_children() {
+                Children::Array(vector) => sop_foreach_json_array(vector, sop, policy),
+                Children::Object(object) => sop_foreach_json_object(object, sop, policy),
+                Children::Scalar(node) => {
+                    let selected = try!(sop_json_value(node, sop, policy));
+                    Ok(selected.unwrap_or(Node::Missing))
+                },
             }
         },
+        &Selector::Descendant(ref sop) => {
+            let mut result = Vec::new();
+            descend_json(node, sop, &mut result);
+            Ok(Node::Array(result))
+        },
+        &Selector::PathChild(ref sop) => Ok(select_segment(node, sop, false)),
+        &Selector::PathDescendant(ref sop) => Ok(select_segment(node, sop, true)),
+    }
+}
+
+fn is_singular(sop: &Sop) -> bool
+{
+    match sop {
+        &Sop::Object(ObjectIndexer::Exact(_)) | &Sop::ArrayIndex(_) => true,
+        _ => false,
     }
 }
 
-fn run_array_action(runtime: &mut Runtime, values: &Vec<Value>, action: &Vec<Function>) -> Result<(), JkError>
+// applies a jsonpath segment to each node of a nodelist. the query starts
+// from a single node, which is kept unwrapped as long as the query is
+// singular (names and indexes only), so '$.store.bicycle' selects the
+// bicycle itself rather than a list holding it.
+fn select_segment<'a>(node: Node<'a>, sop: &Sop, descendant: bool) -> Node<'a>
 {
-    let var_key = &String::from("_k");
-    let var_value = &String::from("_v");
+    let mut result = Vec::new();
 
-    for (i, v) in values.iter().enumerate() {
-        runtime.var_set(var_key, Value::I64(i as i64));
-        runtime.var_set(var_value, v.clone());
-        
-        for func in action {
-            try!(run_function(runtime, v, func));
+    let nodes = match node {
+        Node::Array(nodes) => nodes,
+        node => {
+            if !descendant && is_singular(sop) {
+                sop_children(&node.into_children(), sop, &mut result);
+
+                return match result.pop() {
+                    Some(selected) => selected,
+                    None => Node::Array(result),
+                };
+            }
+
+            vec![node]
+        },
+    };
+
+    for n in nodes {
+        if descendant {
+            descend_json(n, sop, &mut result);
+        } else {
+            sop_children(&n.into_children(), sop, &mut result);
         }
     }
 
-    runtime.var_delete(var_key);
-    runtime.var_delete(var_value);
-
-    return Ok(());
+    return Node::Array(result);
 }
 
-fn run_object_action(runtime: &mut Runtime, object: &Map<String, Value>, action:&Vec<Function>) -> Result<(), JkError>
+// runs the action for one element of a foreach, with `key` as _k
+fn run_element_action(runtime: &mut Runtime, key: Value, node: Node, action: &Vec<Statement>) -> Result<(), JkError>
 {
-    let var_key = &String::from("_k");
-    let var_value = &String::from("_v");
+    let current = &Current::new(Some(key), node);
 
-    for (key, value) in object {
-        runtime.var_set(var_key, Value::String(key.clone()));
-        runtime.var_set(var_value, value.clone());
+    return run_statements(runtime, current, action);
+}
 
-        for func in action {
-            try!(run_function(runtime, value, func));
-        }
+fn run_array_action(runtime: &mut Runtime, nodes: Vec<Node>, action: &Vec<Statement>) -> Result<(), JkError>
+{
+    for (i, node) in nodes.into_iter().enumerate() {
+        try!(run_element_action(runtime, Value::from(i), node, action));
     }
 
-    runtime.var_delete(var_key);
-    runtime.var_delete(var_value);
+    return Ok(());
+}
+
+fn run_object_action(runtime: &mut Runtime, object: IndexMap<String, Node>, action: &Vec<Statement>) -> Result<(), JkError>
+{
+    for (key, node) in object {
+        try!(run_element_action(runtime, Value::String(key), node, action));
+    }
 
     return Ok(());
 }
 
-fn evaluate_object_index(v: &Value, index: &String) -> Result<Value, JkError>
+// an element that is not there is an error, or null where `null_if_missing`
+fn missing_value<'a>(null_if_missing: bool, msg: &str) -> Result<Cow<'a, Value>, JkError>
+{
+    if null_if_missing {
+        return Ok(Cow::Owned(Value::Null));
+    }
+
+    return value_error(msg);
+}
+
+fn evaluate_object_index<'a>(v: &'a Value, index: &String, null_if_missing: bool) -> Result<Cow<'a, Value>, JkError>
 {
     if let &Value::Object(ref obj) = v {
         if let Some(evalue) = obj.get(index) {
-            return Ok(evalue.clone());
+            return Ok(Cow::Borrowed(evalue));
         } else {
-            return value_error("not found in object");
+            return missing_value(null_if_missing, &format!("key {} not found in object", index));
         }
     } else {
-        return value_error("not an object");
+        return missing_value(null_if_missing, &format!("key {} applied to a value that is not an object", index));
     }
 }
 
-fn evaluate_array_index(v: &Value, index: i64) -> Result<Value, JkError>
+fn evaluate_array_index<'a>(v: &'a Value, index: i64, null_if_missing: bool) -> Result<Cow<'a, Value>, JkError>
 {
     if let &Value::Array(ref vector) = v {
         if let Some(evalue) = vector.get(index as usize) {
-            return Ok(evalue.clone());
+            return Ok(Cow::Borrowed(evalue));
         } else {
-            return value_error("out of range");
+            return missing_value(null_if_missing, &format!("index {} out of range for array of length {}", index, vector.len()));
         }
     } else {
-        return value_error("not an array");
+        return missing_value(null_if_missing, &format!("index {} applied to a value that is not an array", index));
     }
 }
 
-fn evaluate(runtime: &Runtime, v: &Value, e: &ActionExpr) -> Result<Value, JkError>
+// _k, _v, _p and _ptr describe the current element; the paths are only
+// formatted when asked for, and null for values built by selectors
+fn evaluate_variable<'a>(runtime: &'a Runtime, current: &'a Current, name: &String) -> Cow<'a, Value>
+{
+    let value = match name.as_str() {
+        "_k" => current.key.as_ref(),
+        "_v" => Some(current.value.as_ref()),
+        "_p" => return Cow::Owned(current.path.as_ref().map_or(Value::Null, |p| Value::String(p.to_jsonpath()))),
+        "_ptr" => return Cow::Owned(current.path.as_ref().map_or(Value::Null, |p| Value::String(p.to_pointer()))),
+        _ => runtime.var_get(name),
+    };
+
+    return value.map_or(Cow::Owned(Value::Null), Cow::Borrowed);
+}
+
+// values are borrowed from the current element where possible, so nothing is
+// copied unless an expression builds a new value. a missing key or index is
+// an error, unless `null_if_missing`, as in conditions.
+fn evaluate<'a>(runtime: &'a Runtime, current: &'a Current, e: &ActionExpr, null_if_missing: bool) -> Result<Cow<'a, Value>, JkError>
 {
     match e {
-        &ActionExpr::Integer(i) => Ok(Value::I64(i)),
-        &ActionExpr::String(ref s) => Ok(Value::String(s.clone())),
-        &ActionExpr::ObjectIndex(ref idx) => evaluate_object_index(v, idx),
-        &ActionExpr::Variable(ref name) => Ok(runtime.var_get(name)),
-        &ActionExpr::ArrayIndex(idx) => evaluate_array_index(v, idx),
+        &ActionExpr::Integer(i) => Ok(Cow::Owned(Value::from(i))),
+        &ActionExpr::Float(f) => Ok(Cow::Owned(Value::from(f))),
+        &ActionExpr::String(ref s) => Ok(Cow::Owned(Value::String(s.clone()))),
+        &ActionExpr::Bool(b) => Ok(Cow::Owned(Value::Bool(b))),
+        &ActionExpr::Null => Ok(Cow::Owned(Value::Null)),
+        &ActionExpr::ObjectIndex(ref idx) => evaluate_object_index(&current.value, idx, null_if_missing),
+        &ActionExpr::Variable(ref name) => Ok(evaluate_variable(runtime, current, name)),
+        &ActionExpr::ArrayIndex(idx) => evaluate_array_index(&current.value, idx, null_if_missing),
+        &ActionExpr::Neg(ref e) => {
+            let v = try!(evaluate(runtime, current, e, null_if_missing));
+            negate(&v).map(Cow::Owned)
+        },
+        &ActionExpr::Arith(op, ref left, ref right) => {
+            let l = try!(evaluate(runtime, current, left, null_if_missing));
+            let r = try!(evaluate(runtime, current, right, null_if_missing));
+            arithmetic(op, &l, &r).map(Cow::Owned)
+        },
+        &ActionExpr::Compare(op, ref left, ref right) => {
+            let l = try!(evaluate(runtime, current, left, null_if_missing));
+            let r = try!(evaluate(runtime, current, right, null_if_missing));
+            Ok(Cow::Owned(Value::Bool(compare_values(op, &l, &r))))
+        },
+        // '&&' and '||' only evaluate their right side when it decides
+        &ActionExpr::And(ref left, ref right) => {
+            let result = try!(test(runtime, current, left)) && try!(test(runtime, current, right));
+            Ok(Cow::Owned(Value::Bool(result)))
+        },
+        &ActionExpr::Or(ref left, ref right) => {
+            let result = try!(test(runtime, current, left)) || try!(test(runtime, current, right));
+            Ok(Cow::Owned(Value::Bool(result)))
+        },
+        &ActionExpr::Not(ref e) => {
+            let result = !try!(test(runtime, current, e));
+            Ok(Cow::Owned(Value::Bool(result)))
+        },
     }
 }
 
-fn batch_evaluate(runtime: &Runtime, v: &Value, expressions: &Vec<ActionExpr>) -> Result<Vec<Value>, JkError>
+// whether an expression holds: null, false, 0 and "" do not, any other
+// value does. a missing key or index counts as null, so that '.a' tests
+// whether the element has one.
+fn test(runtime: &Runtime, current: &Current, e: &ActionExpr) -> Result<bool, JkError>
+{
+    let result = match *try!(evaluate(runtime, current, e, true)) {
+        Value::Null => false,
+        Value::Bool(b) => b,
+        Value::Number(ref n) => n.as_f64() != Some(0.0),
+        Value::String(ref s) => !s.is_empty(),
+        _ => true,
+    };
+
+    return Ok(result);
+}
+
+// integers of either representation, widened so that no operation on two
+// of them overflows before the result is checked
+fn integer_operand(v: &Value) -> Option<i128>
+{
+    v.as_i64().map(|i| i as i128).or_else(|| v.as_u64().map(|u| u as i128))
+}
+
+// an integer result, as a float if it does not fit in 64 bits
+fn integer_result(n: i128) -> Value
+{
+    if n >= i64::MIN as i128 && n <= i64::MAX as i128 {
+        return Value::from(n as i64);
+    }
+
+    if n >= 0 && n <= u64::MAX as i128 {
+        return Value::from(n as u64);
+    }
+
+    return Value::from(n as f64);
+}
+
+fn float_result(op: ArithOp, f: f64) -> Result<Value, JkError>
+{
+    if !f.is_finite() {
+        return Err(JkError::Action(format!("result of '{}' is not a finite number", op)));
+    }
+
+    return Ok(Value::from(f));
+}
+
+// the text a value adds to a string
+fn concat_text(v: &Value) -> String
+{
+    match v {
+        &Value::String(ref s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+// numbers stay integers as long as the result is one, and become floats
+// otherwise; '+' with a string concatenates
+fn arithmetic(op: ArithOp, left: &Value, right: &Value) -> Result<Value, JkError>
+{
+    if op == ArithOp::Add && (left.is_string() || right.is_string()) {
+        return Ok(Value::String(concat_text(left) + &concat_text(right)));
+    }
+
+    if !left.is_number() || !right.is_number() {
+        let expected = if op == ArithOp::Add { "numbers or strings" } else { "numbers" };
+        return Err(JkError::Action(format!("'{}' expects {}, got {} and {}", op, expected, kind_of(left), kind_of(right))));
+    }
+
+    if (op == ArithOp::Div || op == ArithOp::Mod) && right.as_f64() == Some(0.0) {
+        return Err(JkError::Action(String::from("division by zero")));
+    }
+
+    if let (Some(l), Some(r)) = (integer_operand(left), integer_operand(right)) {
+        let n = match op {
+            ArithOp::Add => Some(l + r),
+            ArithOp::Sub => Some(l - r),
+            ArithOp::Mul => l.checked_mul(r),
+            ArithOp::Div => if l % r == 0 { Some(l / r) } else { None },
+            ArithOp::Mod => Some(l % r),
+        };
+
+        if let Some(n) = n {
+            return Ok(integer_result(n));
+        }
+    }
+
+    let (l, r) = (left.as_f64().unwrap(), right.as_f64().unwrap());
+
+    let f = match op {
+        ArithOp::Add => l + r,
+        ArithOp::Sub => l - r,
+        ArithOp::Mul => l * r,
+        ArithOp::Div => l / r,
+        ArithOp::Mod => l % r,
+    };
+
+    return float_result(op, f);
+}
+
+fn negate(v: &Value) -> Result<Value, JkError>
+{
+    if let Some(n) = integer_operand(v) {
+        return Ok(integer_result(-n));
+    }
+
+    match v.as_f64() {
+        Some(f) => Ok(Value::from(-f)),
+        None => Err(JkError::Action(format!("'-' expects a number, got {}", kind_of(v)))),
+    }
+}
+
+fn batch_evaluate<'a>(runtime: &'a Runtime, current: &'a Current, expressions: &Vec<ActionExpr>) -> Result<Vec<Cow<'a, Value>>, JkError>
 {
     let mut evector = Vec::new();
     
     for e in expressions {
-        evector.push(try!(evaluate(runtime, v, e)));
+        evector.push(try!(evaluate(runtime, current, e, false)));
     }
 
     return Ok(evector);
 }
 
-fn run_function(runtime: &mut Runtime, v: &Value, func: &Function) -> Result<(), JkError>
+fn run_function(runtime: &mut Runtime, current: &Current, func: &Function) -> Result<(), JkError>
 {
     if let Some(ref proto) = BUILTIN_FUNCS.get(&func.name) {
-        let args = try!(batch_evaluate(runtime, v, &func.args));
-        try!((proto.func)(&args));
+        let args = try!(batch_evaluate(runtime, current, &func.args));
+        let args: Vec<&Value> = args.iter().map(|a| a.as_ref()).collect();
+        try!((proto.func)(runtime, func, &args));
         return Ok(());
     } else {
-        return action_error("function not found");
+        return action_error(&format!("function {} not found", func.name));
     }
 }
 
-
-fn run_single_action(runtime: &mut Runtime, v: &Value, action: &Vec<Function>) -> Result<(), JkError>
+fn run_statement(runtime: &mut Runtime, current: &Current, statement: &Statement) -> Result<(), JkError>
 {
-    let var_value = &String::from("_v");
-    runtime.var_set(var_value, v.clone());
-    
-    for func in action {
-        try!(run_function(runtime, v, func));
+    match statement {
+        &Statement::Call(ref func) => run_function(runtime, current, func),
+        &Statement::Assign(ref name, ref e) => {
+            if ELEMENT_VARIABLES.contains(&name.as_str()) {
+                return action_error(&format!("cannot assign to {}", name));
+            }
+
+            let value = try!(evaluate(runtime, current, e, false)).into_owned();
+            runtime.var_set(name, value);
+            return Ok(());
+        },
+        // the statements of a branch name themselves when they fail
+        &Statement::If(ref cond, ref then, ref otherwise) => {
+            let holds = try!(test(runtime, current, cond).map_err(|e| statement_error(e, current, &format!("if {}", cond))));
+            return run_statements(runtime, current, if holds { then } else { otherwise });
+        },
     }
+}
+
+fn run_statements(runtime: &mut Runtime, current: &Current, action: &Vec<Statement>) -> Result<(), JkError>
+{
+    for statement in action {
+        let result = run_statement(runtime, current, statement);
 
-    runtime.var_delete(var_value);
+        try!(result.map_err(|e| match statement {
+            &Statement::If(..) => e,
+            statement => statement_error(e, current, statement),
+        }));
+    }
 
     return Ok(());
 }
 
-fn run_foreach_action(value: &Value, action: &Vec<Function>) -> Result<(), JkError>
+// names the statement that failed, and the element it ran on
+fn statement_error(e: JkError, current: &Current, statement: &dyn fmt::Display) -> JkError
 {
-    let runtime = &mut Runtime::new();
-    
-    match value {
-        &Value::Array(ref vector) => run_array_action(runtime, vector, action),
-        &Value::Object(ref object) => run_object_action(runtime, object, action),
-        _ => run_single_action(runtime, value, action),
+    match e {
+        JkError::Action(msg) => {
+            match current.path {
+                Some(ref path) => JkError::Action(format!("in '{}' at {}: {}", statement, path.to_jsonpath(), msg)),
+                None => JkError::Action(format!("in '{}': {}", statement, msg)),
+            }
+        },
+        e => e,
+    }
+}
+
+
+fn run_single_action(runtime: &mut Runtime, node: Node, action: &Vec<Statement>) -> Result<(), JkError>
+{
+    let current = &Current::new(None, node);
+
+    return run_statements(runtime, current, action);
+}
+
+fn run_foreach_action(runtime: &mut Runtime, node: Node, action: &Vec<Statement>) -> Result<(), JkError>
+{
+    match node.into_children() {
+        Children::Array(nodes) => run_array_action(runtime, nodes, action),
+        Children::Object(object) => run_object_action(runtime, object, action),
+        Children::Scalar(node) => run_single_action(runtime, node, action),
     }
 }
 
-fn run_forself_action(value: &Value, action: &Vec<Function>) -> Result<(), JkError>
+fn run_forself_action(runtime: &mut Runtime, node: Node, action: &Vec<Statement>) -> Result<(), JkError>
 {
-    let runtime = &mut Runtime::new();
-    return run_single_action(runtime, value, action);
+    return run_single_action(runtime, node, action);
 }
 
-fn execute<R: io::Read>(script: &Script, reader: &mut R) -> Result<(), JkError>
+// advances NR, the number of the record being processed. records are
+// counted from 1 across all the input files.
+fn next_record(runtime: &mut Runtime)
 {
-    let selector = &script.selector;
-    let action = &script.action;
+    let var_nr = &String::from("NR");
+    let nr = runtime.var_get(var_nr).and_then(Value::as_u64).unwrap_or(0);
+
+    runtime.var_set(var_nr, Value::from(nr + 1));
+}
+
+fn run_script(runtime: &mut Runtime, script: &Script, options: &Options, root: &Value) -> Result<(), JkError>
+{
+    let mut json_curr = Node::root(root);
+
+    for s in &script.selector {
+        let json_next = try!(select_json(json_curr, s, options.missing));
+        json_curr = json_next;
+    }
+
+    match script.mode {
+        ActionMode::ForEach => return run_foreach_action(runtime, json_curr, &script.action),
+        ActionMode::ForSelf => return run_forself_action(runtime, json_curr, &script.action),
+    }
+}
 
+fn execute<R: io::Read>(runtime: &mut Runtime, script: &Script, options: &Options, mut reader: R) -> Result<(), JkError>
+{
     let mut input = String::new();
 
     try!(reader.read_to_string(&mut input).map_err(JkError::Io));
     
-    let json_root: Value = try!(json::from_str(&input).map_err(JkError::Parse));
-    let mut json_curr = json_root;
+    let parsed = match options.input {
+        InputFormat::Json => json::from_str(&input).map_err(json_error),
+        InputFormat::Json5 => lenient::from_str(&input),
+        InputFormat::Yaml => {
+            let documents = try!(yaml::documents(&input).map_err(|e| with_error_text(e, &input)));
+            return run_records(runtime, script, options, documents.into_iter().map(Ok));
+        },
+    };
 
-    for s in selector {
-        let json_next = try!(select_json(json_curr, s));
-        json_curr = json_next;
+    let json_root = try!(parsed.map_err(|e| with_error_text(e, &input)));
+
+    next_record(runtime);
+    return run_script(runtime, script, options, &json_root);
+}
+
+// runs the program once for each top-level value of the input, as found in
+// json lines, where each line holds a record, or in concatenated json
+fn execute_records<R: io::Read>(runtime: &mut Runtime, script: &Script, options: &Options, mut reader: R) -> Result<(), JkError>
+{
+    match options.input {
+        InputFormat::Json => {
+            let mut input = RecentLines::new(io::BufReader::new(reader));
+            let result = {
+                let records = json::Deserializer::from_reader(&mut input).into_iter::<Value>();
+                run_records(runtime, script, options, records.map(|r| r.map_err(json_error)))
+            };
+            result.map_err(|e| input.error_text(e))
+        },
+        InputFormat::Json5 => {
+            let mut input = String::new();
+            try!(reader.read_to_string(&mut input).map_err(JkError::Io));
+            let records = lenient::Parser::new(&input).map(|r| r.map_err(|e| with_error_text(e, &input)));
+            run_records(runtime, script, options, records)
+        },
+        InputFormat::Yaml => execute(runtime, script, options, reader),
     }
+}
 
-    match script.mode {
-        ActionMode::ForEach => return run_foreach_action(&json_curr, action),
-        ActionMode::ForSelf => return run_forself_action(&json_curr, action),
+fn run_records<I>(runtime: &mut Runtime, script: &Script, options: &Options, records: I) -> Result<(), JkError>
+    where I: Iterator<Item=Result<Value, JkError>>
+{
+    for record in records {
+        let record = try!(record);
+
+        next_record(runtime);
+        try!(run_script(runtime, script, options, &record));
     }
+
+    return Ok(());
 }
 
-fn main() {
-    if let Some(program) = env::args().nth(1) {
-        match script(&program) {
-            Ok(s) => { execute(&s, &mut io::stdin()).unwrap(); },
-            Err(e) => println!("parse error, program={} error={:?}", program, e),
+// splits a program into the selectors that can be followed while scanning
+// (keys and indexes), and an optional foreach selector applied to each
+// streamed element. None if the program needs the whole document.
+fn stream_plan(script: &Script) -> Option<(Vec<&Sop>, Option<&Sop>)>
+{
+    if let ActionMode::ForSelf = script.mode {
+        return None;
+    }
+
+    let mut prefix = Vec::new();
+    let mut each = None;
+
+    for selector in &script.selector {
+        if each.is_some() {
+            return None;
+        }
+
+        match selector {
+            &Selector::ForSelf(ref sop @ Sop::Object(ObjectIndexer::Exact(_))) |
+            &Selector::ForSelf(ref sop @ Sop::PointerToken(_)) => prefix.push(sop),
+            &Selector::ForSelf(ref sop @ Sop::ArrayIndex(_)) if !is_negative_index(sop) => prefix.push(sop),
+            &Selector::ForEach(ref sop) => each = Some(sop),
+            _ => return None,
         }
+    }
+
+    return Some((prefix, each));
+}
+
+fn is_negative_index(sop: &Sop) -> bool
+{
+    match sop {
+        &Sop::ArrayIndex(index) => index < 0,
+        _ => false,
+    }
+}
+
+// what a selector finds in a document being scanned
+enum Seek {
+    // the member it selects, which the scanner is now at
+    Member(Path),
+    // why there is no such member
+    Missing(String),
+    // a value that is not an object or array the selector picks a member
+    // of, which the scanner is still at: it is read whole instead
+    Other,
+}
+
+// moves the scanner to the member of the next value that `sop` selects
+fn stream_seek<R: io::Read>(scanner: &mut Scanner<R>, sop: &Sop, path: &Path) -> Result<Seek, JkError>
+{
+    let key = match sop {
+        &Sop::Object(ObjectIndexer::Exact(ref key)) | &Sop::PointerToken(ref key) => Some(key),
+        _ => None,
+    };
+
+    let index = match sop {
+        &Sop::ArrayIndex(index) => Some(index as usize),
+        &Sop::PointerToken(ref token) => pointer_index(token),
+        _ => None,
+    };
+
+    match try!(scanner.kind()) {
+        stream::Kind::Object if key.is_some() => {
+            try!(scanner.begin_object());
+            while let Some(k) = try!(scanner.next_key()) {
+                if Some(&k) == key {
+                    return Ok(Seek::Member(path.key(&k)));
+                }
+                try!(scanner.skip_value());
+            }
+            return Ok(Seek::Missing(format!("selector {} not found in object", sop)));
+        },
+        stream::Kind::Array if index.is_some() => {
+            try!(scanner.begin_array());
+            let mut i = 0;
+            while try!(scanner.next_element()) {
+                if Some(i) == index {
+                    return Ok(Seek::Member(path.index(i)));
+                }
+                try!(scanner.skip_value());
+                i += 1;
+            }
+            return Ok(Seek::Missing(format!("selector {} out of range for array of length {}", sop, i)));
+        },
+        _ => return Ok(Seek::Other),
+    }
+}
+
+// runs a foreach program over a document read incrementally: the selected
+// array or object is never loaded as a whole, each of its elements is
+// parsed, acted on and dropped in turn
+fn execute_stream<R: io::Read>(runtime: &mut Runtime, script: &Script, options: &Options, reader: R) -> Result<(), JkError>
+{
+    let mut input = RecentLines::new(io::BufReader::new(reader));
+    let result = stream_document(runtime, script, options, Scanner::new(&mut input));
+    return result.map_err(|e| input.error_text(e));
+}
+
+fn stream_document<R: io::Read>(runtime: &mut Runtime, script: &Script, options: &Options, mut scanner: Scanner<R>) -> Result<(), JkError>
+{
+    let (prefix, each) = try!(stream_plan(script).ok_or(JkError::Query(String::from(
+        "only keys, indexes and one foreach selector followed by the '%' mode can be streamed"))));
+
+    let action = &script.action;
+    let mut path = Path::root();
+
+    next_record(runtime);
+
+    for (i, sop) in prefix.into_iter().enumerate() {
+        match try!(stream_seek(&mut scanner, sop, &path)) {
+            Seek::Member(next) => path = next,
+            Seek::Missing(msg) => {
+                try!(scanner.finish());
+                try!(missing_node(options.missing, msg));
+                return run_single_action(runtime, Node::Missing, action);
+            },
+            // the rest of the program runs as it would on the whole document,
+            // e.g. a key applied to each element of an array
+            Seek::Other => {
+                let value = try!(scanner.read_value());
+                try!(scanner.finish());
+
+                let mut node = Node::Value(&value, path);
+                for selector in &script.selector[i..] {
+                    node = try!(select_json(node, selector, options.missing));
+                }
+                return run_foreach_action(runtime, node, action);
+            },
+        }
+    }
+
+    // a foreach selector turns the elements into an array of its results
+    let mut count = 0;
+    let mut run = |runtime: &mut Runtime, key: Value, node: Node| -> Result<(), JkError> {
+        match each {
+            Some(sop) => {
+                if let Some(selected) = try!(sop_json_value(node, sop, options.missing)) {
+                    try!(run_element_action(runtime, Value::from(count), selected, action));
+                    count += 1;
+                }
+                Ok(())
+            },
+            None => run_element_action(runtime, key, node, action),
+        }
+    };
+
+    match try!(scanner.kind()) {
+        stream::Kind::Array => {
+            try!(scanner.begin_array());
+            let mut i = 0;
+            while try!(scanner.next_element()) {
+                let value = try!(scanner.read_value());
+                try!(run(runtime, Value::from(i), Node::Value(&value, path.index(i))));
+                i += 1;
+            }
+        },
+        stream::Kind::Object => {
+            try!(scanner.begin_object());
+            while let Some(key) = try!(scanner.next_key()) {
+                let value = try!(scanner.read_value());
+                let child = path.key(&key);
+                try!(run(runtime, Value::String(key), Node::Value(&value, child)));
+            }
+        },
+        stream::Kind::Scalar => {
+            let value = try!(scanner.read_value());
+            try!(scanner.finish());
+            let node = Node::Value(&value, path);
+            return match each {
+                Some(sop) => {
+                    let selected = try!(sop_json_value(node, sop, options.missing));
+                    run_single_action(runtime, selected.unwrap_or(Node::Missing), action)
+                },
+                None => run_single_action(runtime, node, action),
+            };
+        },
+    }
+
+    // the rest of the document is still checked
+    return scanner.finish();
+}
+
+fn open_input(name: &str) -> Result<Box<dyn io::Read>, JkError>
+{
+    if name == "-" {
+        return Ok(Box::new(io::stdin()));
+    }
+
+    match File::open(name) {
+        Ok(file) => Ok(Box::new(file)),
+        Err(e) => Err(JkError::Io(e)),
+    }
+}
+
+// line `line` of a file, to show a syntax error found while streaming it
+fn file_line(name: &str, line: usize) -> Option<String>
+{
+    let file = match File::open(name) {
+        Ok(file) => file,
+        Err(_) => return None,
+    };
+
+    io::BufReader::new(file).lines().nth(line - 1).and_then(|l| l.ok())
+}
+
+// places an error in the file it happened in, and in the record if the
+// input is made of records
+fn input_error(e: JkError, runtime: &Runtime, name: &str, options: &Options) -> JkError
+{
+    let e = match e {
+        JkError::Parse(mut e) => {
+            if e.text.is_none() && e.line > 0 && name != "-" {
+                e.text = file_line(name, e.line);
+            }
+            JkError::Parse(e)
+        },
+        e => e,
+    };
+
+    let records = options.lines || options.input == InputFormat::Yaml;
+    let record = runtime.var_get(&String::from("NR")).and_then(Value::as_u64).filter(|_| records);
+    let file = if name == "-" { "<stdin>" } else { name };
+
+    return JkError::Input(Box::new(e), String::from(file), record);
+}
+
+// runs the program over each input file in turn, or over stdin if none is
+// given. FILENAME names the file being read, '-' for stdin.
+fn execute_files(script: &Script, options: &Options) -> Result<(), JkError>
+{
+    let runtime = &mut Runtime::new(Output::new(options.output, options.values, options.header, options.delimiter));
+
+    for &(ref name, ref value) in &options.variables {
+        runtime.var_set(name, Value::String(value.clone()));
+    }
+    let stdin = [String::from("-")];
+    let files = if options.files.is_empty() { &stdin[..] } else { &options.files[..] };
+
+    for name in files {
+        let result = open_input(name).and_then(|reader| {
+            runtime.var_set(&String::from("FILENAME"), Value::String(name.clone()));
+
+            if options.stream {
+                execute_stream(runtime, script, options, reader)
+            } else if options.lines {
+                execute_records(runtime, script, options, reader)
+            } else {
+                execute(runtime, script, options, reader)
+            }
+        });
+
+        if let Err(e) = result {
+            // the rows held back so far are still printed
+            runtime.output.borrow_mut().finish();
+            return Err(input_error(e, runtime, name, options));
+        }
+    }
+
+    runtime.output.borrow_mut().finish();
+    return Ok(());
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1)) {
+        Ok(o) => o,
+        Err(msg) => {
+            eprintln!("jk: {}", msg);
+            process::exit(2);
+        },
+    };
+
+    let parsed = if options.jsonpath {
+        jsonpath_script(&options.program)
     } else {
-        println!("at least one argument must be supplied");
+        script(&options.program)
+    };
+
+    match parsed {
+        Ok(s) => {
+            if let Err(e) = execute_files(&s, &options) {
+                eprintln!("jk: {}", e);
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("jk: {}", syntax::describe(&options.program, &e, options.jsonpath));
+            process::exit(2);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use output::{OutputFormat, ValueFormat};
+
+    // cases of the compliance suite that need what -j does not support: '$'
+    // queries inside filters, and spaces at the end of a query, which the
+    // mode separator takes
+    const KNOWN_FAILURES: &'static [&'static str] = &[
+        "basic, no trailing whitespace",
+        "filter, absolute existence test",
+        "filter, absolute singular query in comparison",
+        "filter, relative and absolute singular queries",
+    ];
+
+    // the nodelist a jsonpath query selects from `document`, or None if the
+    // query does not parse
+    fn query(selector: &str, document: &Value) -> Option<Vec<Value>> {
+        let script = match jsonpath_script(&format!("{}@ p _v", selector)) {
+            Ok(script) => script,
+            Err(_) => return None,
+        };
+
+        let mut node = Node::root(document);
+        for selector in &script.selector {
+            node = select_json(node, selector, MissingPolicy::Error).unwrap();
+        }
+
+        // a singular query yields the node it selects rather than a list
+        match node {
+            Node::Array(nodes) => Some(nodes.iter().map(Node::to_value).collect()),
+            node => Some(vec![node.to_value()]),
+        }
+    }
+
+    // what the selector of a jk program selects from `document`
+    fn select(program: &str, document: &str, policy: MissingPolicy) -> Value {
+        let script = script(program).unwrap();
+        let document: Value = json::from_str(document).unwrap();
+
+        let mut node = Node::root(&document);
+        for selector in &script.selector {
+            node = select_json(node, selector, policy).unwrap();
+        }
+
+        return node.to_value();
+    }
+
+    #[test]
+    fn filter_on_scalars() {
+        let document = r#"{"a": [1, 5, 3], "s": "red"}"#;
+        let value = |text| json::from_str::<Value>(text).unwrap();
+
+        // each element is kept or dropped as the filter says
+        assert_eq!(select(".a->[?(@ > 2)] @ p _v", document, MissingPolicy::Error), value("[5, 3]"));
+        assert_eq!(select(".a->[?(@ > 2)] @ p _v", document, MissingPolicy::Null), value("[null, 5, 3]"));
+        assert_eq!(select(r#".s.[?(@ == "red")] @ p _v"#, document, MissingPolicy::Error), value(r#""red""#));
+        assert_eq!(select(r#".s.[?(@ == "blue")] @ p _v"#, document, MissingPolicy::Error), Value::Null);
+    }
+
+    // streams `document` through a -s program, whose action counts the
+    // elements in n and keeps the last one in v
+    fn stream(selector: &str, document: &str) -> Result<(Value, Value), JkError> {
+        let args = vec!["-s", "-m", "null", selector];
+        let options = parse_options(args.into_iter().map(String::from)).unwrap();
+        let script = script(&format!("{} % n = n + 1; v = _v", options.program)).unwrap();
+
+        let mut runtime = Runtime::new(Output::new(OutputFormat::Text, ValueFormat::Pretty, false, ','));
+        runtime.var_set(&String::from("n"), Value::from(0));
+        try!(execute_stream(&mut runtime, &script, &options, document.as_bytes()));
+
+        let var = |name: &str| runtime.var_get(name).cloned().unwrap_or(Value::Null);
+        return Ok((var("n"), var("v")));
+    }
+
+    #[test]
+    fn stream_like_whole_document() {
+        let store = r#"{"store": {"book": [{"title": "Moby Dick"}, {"title": "Sword of Honour"}], "x": 1}}"#;
+        let value = |text| json::from_str::<Value>(text).unwrap();
+        assert_eq!(stream(".store.book", store).unwrap(), (Value::from(2), value(r#"{"title": "Sword of Honour"}"#)));
+
+        // a key applied to an array selects it from each element
+        assert_eq!(stream(".store.book.title", store).unwrap(), (Value::from(2), Value::from("Sword of Honour")));
+        assert_eq!(stream(".store.x", store).unwrap(), (Value::from(1), Value::from(1)));
+        assert_eq!(stream(".store.nope", store).unwrap(), (Value::from(1), Value::Null));
+
+        // what follows the selection is still read
+        for document in &["[1, 2] garbage", r#"{"items": [1, 2] , "x": }"#, r#"{"items": [1, 2]"#] {
+            match stream(".items", document) {
+                Err(JkError::Parse(_)) => {},
+                r => panic!("{} gave {:?}", document, r),
+            }
+        }
+    }
+
+    // whether `cond` holds for `element`, as the condition of an 'if'
+    fn holds(cond: &str, element: &str) -> Result<bool, JkError> {
+        let script = script(&format!(".a @ if {} {{ p 1 }}", cond)).unwrap();
+        let cond = match script.action[0] {
+            Statement::If(ref cond, _, _) => cond,
+            _ => unreachable!(),
+        };
+
+        let runtime = Runtime::new(Output::new(OutputFormat::Text, ValueFormat::Pretty, false, ','));
+        let element: Value = json::from_str(element).unwrap();
+        return test(&runtime, &Current::new(None, Node::root(&element)), cond);
+    }
+
+    #[test]
+    fn missing_in_conditions() {
+        let book = r#"{"title": "Moby Dick", "price": 8.99, "isbn": "0-553-21311-3", "tags": ["sea"]}"#;
+        assert_eq!(holds(".isbn", book).unwrap(), true);
+        assert_eq!(holds(".isbn", r#"{"price": 8.99}"#).unwrap(), false);
+        assert_eq!(holds(".price > 5 && .isbn", book).unwrap(), true);
+        assert_eq!(holds(".price > 5 && .isbn", r#"{"price": 8.99}"#).unwrap(), false);
+        assert_eq!(holds("!.isbn && .isbn == null", "{}").unwrap(), true);
+        assert_eq!(holds(".isbn == null", r#"{"isbn": null}"#).unwrap(), true);
+        assert_eq!(holds(".isbn == null", book).unwrap(), false);
+        assert_eq!(holds(".sold == false", r#"{"sold": false}"#).unwrap(), true);
+        assert_eq!(holds(".sold == false", r#"{"sold": 0}"#).unwrap(), false);
+        assert_eq!(holds(".sold == false", "{}").unwrap(), false);
+        assert_eq!(holds(".sold != true", "{}").unwrap(), true);
+        assert_eq!(holds(".[1]", "[1]").unwrap(), false);
+        assert_eq!(holds(".isbn", "[1]").unwrap(), false);
+
+        // what is there is still checked
+        let e = holds(".price + .tax > 10", r#"{"price": 8.99}"#).unwrap_err();
+        assert_eq!(e.to_string(), "action error: '+' expects numbers or strings, got a number and null");
+    }
+
+    // runs tests/cts.json, in the format of the rfc 9535 compliance test
+    // suite: a query either is invalid, or selects the nodes of `result`, or
+    // those of one of `results` when their order is not fixed
+    #[test]
+    fn jsonpath_compliance() {
+        let suite: Value = json::from_str(include_str!("../tests/cts.json")).unwrap();
+        let mut failures = Vec::new();
+
+        for case in suite["tests"].as_array().unwrap() {
+            let name = case["name"].as_str().unwrap();
+            let nodes = query(case["selector"].as_str().unwrap(), &case["document"]).map(Value::Array);
+
+            let passed = match nodes {
+                None => case["invalid_selector"] == Value::Bool(true),
+                Some(ref nodes) if case["results"].is_array() => case["results"].as_array().unwrap().contains(nodes),
+                Some(ref nodes) => case["result"] == *nodes,
+            };
+
+            if passed == KNOWN_FAILURES.contains(&name) {
+                let state = if passed { "passes, but is listed as a known failure" } else { "fails" };
+                failures.push(format!("{} {:?}: {}", state, case["selector"], name));
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
     }
 }
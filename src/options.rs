@@ -0,0 +1,214 @@
+use std::string::String;
+
+use script::{MissingPolicy, ELEMENT_VARIABLES};
+use output::{OutputFormat, ValueFormat, parse_output_format, parse_value_format};
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    Json,
+    // json5, which also covers jsonc
+    Json5,
+    // each document of a yaml stream is a record
+    Yaml,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub program: String,
+    pub missing: MissingPolicy,
+    pub jsonpath: bool,
+    pub stream: bool,
+    pub lines: bool,
+    pub input: InputFormat,
+    pub output: OutputFormat,
+    // how text output shows values
+    pub values: ValueFormat,
+    // print a header row naming the columns
+    pub header: bool,
+    // the field delimiter of csv output
+    pub delimiter: char,
+    // variables set before the input is read, from -v NAME=VALUE
+    pub variables: Vec<(String, String)>,
+    // input files, read in order; stdin if empty
+    pub files: Vec<String>,
+}
+
+const USAGE: &'static str = "usage: jk [-j] [-s|-l] [-i json|json5|yaml] [-o text|table|csv|tsv] [-f pretty|compact|json] [-d DELIM] [-H] [-v NAME=VALUE]... [-m error|null|skip] PROGRAM [FILE...]";
+
+
+fn parse_missing_policy(name: &str) -> Result<MissingPolicy, String>
+{
+    match name {
+        "error" => Ok(MissingPolicy::Error),
+        "null" => Ok(MissingPolicy::Null),
+        "skip" => Ok(MissingPolicy::Skip),
+        _ => Err(format!("unknown missing policy '{}', expected error, null or skip", name)),
+    }
+}
+
+fn parse_input_format(name: &str) -> Result<InputFormat, String>
+{
+    match name {
+        "json" => Ok(InputFormat::Json),
+        "json5" | "jsonc" => Ok(InputFormat::Json5),
+        "yaml" | "yml" => Ok(InputFormat::Yaml),
+        _ => Err(format!("unknown input format '{}', expected json, json5 or yaml", name)),
+    }
+}
+
+fn parse_delimiter(value: &str) -> Result<char, String>
+{
+    let mut chars = value.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c != '"' && c != '\n' && c != '\r' => Ok(c),
+        _ => Err(format!("invalid delimiter '{}', expected a single character other than a quote or line break", value)),
+    }
+}
+
+// NAME=VALUE, where the value may hold the escapes \t, \n, \r, \0 and \\,
+// as in awk
+fn parse_assignment(assignment: &str) -> Result<(String, String), String>
+{
+    let (name, value) = match assignment.find('=') {
+        Some(eq) => (&assignment[..eq], &assignment[eq + 1..]),
+        None => return Err(format!("invalid assignment '{}', expected NAME=VALUE", assignment)),
+    };
+
+    let mut chars = name.chars();
+    let valid = chars.next().map_or(false, |c| c == '_' || c.is_ascii_alphabetic()) && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+
+    if !valid || ELEMENT_VARIABLES.contains(&name) {
+        return Err(format!("invalid variable name '{}'", name));
+    }
+
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('0') => unescaped.push('\0'),
+            Some('\\') => unescaped.push('\\'),
+            Some(c) => { unescaped.push('\\'); unescaped.push(c); },
+            None => unescaped.push('\\'),
+        }
+    }
+
+    return Ok((String::from(name), unescaped));
+}
+
+// splits "--name=value" into its name and inline value
+fn split_option(arg: &str) -> (&str, Option<&str>)
+{
+    if arg.starts_with("--") {
+        if let Some(eq) = arg.find('=') {
+            return (&arg[..eq], Some(&arg[eq + 1..]));
+        }
+    }
+
+    return (arg, None);
+}
+
+fn option_value<I: Iterator<Item=String>>(name: &str, inline: Option<&str>, args: &mut I) -> Result<String, String>
+{
+    if let Some(value) = inline {
+        return Ok(String::from(value));
+    }
+
+    return args.next().ok_or(format!("option {} requires a value", name));
+}
+
+pub fn parse_options<I: Iterator<Item=String>>(mut args: I) -> Result<Options, String>
+{
+    let mut program = None;
+    let mut missing = MissingPolicy::Error;
+    let mut jsonpath = false;
+    let mut stream = false;
+    let mut lines = false;
+    let mut input = InputFormat::Json;
+    let mut output = OutputFormat::Text;
+    let mut values = None;
+    let mut header = false;
+    let mut delimiter = None;
+    let mut variables = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let (name, inline) = split_option(&arg);
+
+        match name {
+            "-m" | "--missing" => {
+                let value = try!(option_value(name, inline, &mut args));
+                missing = try!(parse_missing_policy(&value));
+            },
+            "-i" | "--input" => {
+                let value = try!(option_value(name, inline, &mut args));
+                input = try!(parse_input_format(&value));
+            },
+            "-o" | "--output" => {
+                let value = try!(option_value(name, inline, &mut args));
+                output = try!(parse_output_format(&value));
+            },
+            "-f" | "--format" => {
+                let value = try!(option_value(name, inline, &mut args));
+                values = Some(try!(parse_value_format(&value)));
+            },
+            "-d" | "--delimiter" => {
+                let value = try!(option_value(name, inline, &mut args));
+                delimiter = Some(try!(parse_delimiter(&value)));
+            },
+            "-H" | "--header" => header = true,
+            "-v" | "--assign" => {
+                let value = try!(option_value(name, inline, &mut args));
+                variables.push(try!(parse_assignment(&value)));
+            },
+            "-j" | "--jsonpath" => jsonpath = true,
+            "-s" | "--stream" => stream = true,
+            "-l" | "--lines" => lines = true,
+            "--" => {
+                program = args.next();
+                break;
+            },
+            // a program may itself start with the '->' selector
+            _ if name.starts_with("-") && !name.starts_with("->") && name.len() > 1 => {
+                return Err(format!("unknown option {}\n{}", name, USAGE));
+            },
+            _ => {
+                program = Some(arg.clone());
+                break;
+            },
+        }
+    }
+
+    // everything after the program names an input file
+    let files = args.collect();
+
+    if stream && lines {
+        return Err(format!("options -s and -l cannot be used together\n{}", USAGE));
+    }
+
+    if stream && input != InputFormat::Json {
+        return Err(format!("option -s only reads json input\n{}", USAGE));
+    }
+
+    if values.is_some() && output != OutputFormat::Text {
+        return Err(format!("option -f only applies to text output\n{}", USAGE));
+    }
+
+    if delimiter.is_some() && output != OutputFormat::Csv {
+        return Err(format!("option -d only applies to csv output\n{}", USAGE));
+    }
+
+    match program {
+        Some(program) => Ok(Options { program: program, missing: missing, jsonpath: jsonpath, stream: stream, lines: lines, input: input, output: output, values: values.unwrap_or(ValueFormat::Pretty), header: header, delimiter: delimiter.unwrap_or(','), variables: variables, files: files }),
+        None => Err(format!("at least one argument must be supplied\n{}", USAGE)),
+    }
+}
@@ -0,0 +1,253 @@
+use std::mem;
+
+use json;
+use json::Value;
+
+use unicode_width::UnicodeWidthStr;
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    // each row as it is printed, values separated by OFS and ended by ORS
+    Text,
+    // rows held back until the end and printed as aligned columns
+    Table,
+    // rfc 4180 comma separated values, with a configurable delimiter
+    Csv,
+    // tab separated values, with tabs and line breaks in values escaped
+    Tsv,
+}
+
+pub fn parse_output_format(name: &str) -> Result<OutputFormat, String>
+{
+    match name {
+        "text" => Ok(OutputFormat::Text),
+        "table" => Ok(OutputFormat::Table),
+        "csv" => Ok(OutputFormat::Csv),
+        "tsv" => Ok(OutputFormat::Tsv),
+        _ => Err(format!("unknown output format '{}', expected text, table, csv or tsv", name)),
+    }
+}
+
+// how text output shows the values printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueFormat {
+    // strings as they are, other values as indented json
+    Pretty,
+    // strings as they are, other values as json on one line
+    Compact,
+    // every value as json on one line, strings quoted, so that a row of one
+    // value is a line of json lines
+    Json,
+}
+
+pub fn parse_value_format(name: &str) -> Result<ValueFormat, String>
+{
+    match name {
+        "pretty" => Ok(ValueFormat::Pretty),
+        "compact" => Ok(ValueFormat::Compact),
+        "json" => Ok(ValueFormat::Json),
+        _ => Err(format!("unknown value format '{}', expected pretty, compact or json", name)),
+    }
+}
+
+// a printed value, as shown in a row
+struct Cell {
+    text: String,
+    // columns of numbers only are aligned to the right
+    number: bool,
+}
+
+// where the rows printed by the actions go
+pub struct Output {
+    format: OutputFormat,
+    values: ValueFormat,
+    // the delimiter of csv fields
+    delimiter: char,
+    // whether a header row names the columns; it is taken from the first row
+    // printed, and is pending until then
+    header: bool,
+    // the header row and the rows held back by a table
+    names: Vec<Cell>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, values: ValueFormat, header: bool, delimiter: char) -> Output
+    {
+        Output {
+            format: format,
+            values: values,
+            delimiter: delimiter,
+            header: header,
+            names: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    // prints a row of values. `names` gives the names of the columns, for
+    // the header. `compact` keeps pretty values on one line. text rows are
+    // separated by `ors`, their values by `ofs`.
+    pub fn row<F: FnOnce() -> Vec<String>>(&mut self, names: F, values: &[&Value], compact: bool, ofs: &str, ors: &str) -> Result<(), json::Error>
+    {
+        let names = if self.header { Some(names()) } else { None };
+        self.header = false;
+
+        match self.format {
+            OutputFormat::Text => {
+                if let Some(names) = names {
+                    print!("{}{}", names.join(ofs), ors);
+                }
+
+                let format = match self.values {
+                    ValueFormat::Pretty if compact => ValueFormat::Compact,
+                    format => format,
+                };
+
+                let mut line = String::new();
+
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        line.push_str(ofs);
+                    }
+
+                    match (*v, format) {
+                        (&Value::String(ref s), ValueFormat::Pretty) | (&Value::String(ref s), ValueFormat::Compact) => line.push_str(s),
+                        (_, ValueFormat::Pretty) => line.push_str(&try!(json::to_string_pretty(v))),
+                        _ => line.push_str(&try!(json::to_string(v))),
+                    }
+                }
+
+                print!("{}{}", line, ors);
+            },
+            OutputFormat::Table => {
+                if let Some(names) = names {
+                    self.names = names.into_iter().map(|name| Cell { text: name, number: false }).collect();
+                }
+
+                let mut row = Vec::new();
+                for v in values {
+                    row.push(try!(table_cell(v)));
+                }
+                self.rows.push(row);
+            },
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                if let Some(names) = names {
+                    let fields: Vec<String> = names.iter().map(|name| self.field(name)).collect();
+                    print!("{}\n", fields.join(&self.separator()));
+                }
+
+                let mut fields = Vec::new();
+                for v in values {
+                    fields.push(match *v {
+                        // left empty, and so told apart from an empty
+                        // string, which csv quotes
+                        &Value::Null => String::new(),
+                        &Value::String(ref s) => self.field(s),
+                        _ => self.field(&try!(json::to_string(v))),
+                    });
+                }
+                print!("{}\n", fields.join(&self.separator()));
+            },
+        }
+
+        return Ok(());
+    }
+
+    pub fn format(&self) -> OutputFormat
+    {
+        self.format
+    }
+
+    // prints text as it is
+    pub fn text(&mut self, text: &str)
+    {
+        print!("{}", text);
+    }
+
+    fn separator(&self) -> String
+    {
+        match self.format {
+            OutputFormat::Tsv => String::from("\t"),
+            _ => self.delimiter.to_string(),
+        }
+    }
+
+    // a csv or tsv field holding `text`
+    fn field(&self, text: &str) -> String
+    {
+        if self.format == OutputFormat::Tsv {
+            return text.replace("\\", "\\\\").replace("\t", "\\t").replace("\n", "\\n").replace("\r", "\\r");
+        }
+
+        let quoted = text.is_empty() || text.contains(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r');
+
+        if quoted {
+            return format!("\"{}\"", text.replace("\"", "\"\""));
+        }
+
+        return String::from(text);
+    }
+
+    // prints the rows held back, once all input is read
+    pub fn finish(&mut self)
+    {
+        let mut widths: Vec<usize> = Vec::new();
+        let mut numbers: Vec<bool> = Vec::new();
+
+        for (r, row) in self.rows.iter().chain(Some(&self.names)).enumerate() {
+            for (i, cell) in row.iter().enumerate() {
+                let width = UnicodeWidthStr::width(cell.text.as_str());
+                if i == widths.len() {
+                    widths.push(width);
+                    numbers.push(true);
+                } else if widths[i] < width {
+                    widths[i] = width;
+                }
+                // the header, chained last, does not count
+                if r < self.rows.len() && !cell.number {
+                    numbers[i] = false;
+                }
+            }
+        }
+
+        let header = if self.names.is_empty() { None } else { Some(mem::replace(&mut self.names, Vec::new())) };
+
+        for row in header.into_iter().chain(self.rows.drain(..)) {
+            let mut line = String::new();
+
+            for (i, cell) in row.iter().enumerate() {
+                let padding = " ".repeat(widths[i] - UnicodeWidthStr::width(cell.text.as_str()));
+
+                if i > 0 {
+                    line.push_str("  ");
+                }
+
+                if numbers[i] {
+                    line.push_str(&padding);
+                    line.push_str(&cell.text);
+                } else {
+                    line.push_str(&cell.text);
+                    // the last column is not padded, lest lines end in spaces
+                    if i + 1 < row.len() {
+                        line.push_str(&padding);
+                    }
+                }
+            }
+
+            println!("{}", line);
+        }
+    }
+}
+
+fn table_cell(value: &Value) -> Result<Cell, json::Error>
+{
+    let text = match value {
+        // a line break or tab inside a string would break the table
+        &Value::String(ref s) => s.replace("\n", "\\n").replace("\t", "\\t").replace("\r", "\\r"),
+        // nested values are kept on one line
+        _ => try!(json::to_string(value)),
+    };
+
+    return Ok(Cell { text: text, number: value.is_number() });
+}
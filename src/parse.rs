@@ -1,7 +1,7 @@
 // Generated by rust-peg. Do not edit.
-#![allow(non_snake_case, unused)]
+use self::RuleResult::{Failed, Matched};
+use json::Value;
 use script::*;
-use self::RuleResult::{Matched, Failed};
 fn escape_default(s: &str) -> String {
     s.chars().flat_map(|c| c.escape_default()).collect()
 }
@@ -11,7 +11,10 @@ fn char_range_at(s: &str, pos: usize) -> (char, usize) {
     (*c, next_pos)
 }
 #[derive(Clone)]
-enum RuleResult<T> { Matched(usize, T), Failed, }
+enum RuleResult<T> {
+    Matched(usize, T),
+    Failed,
+}
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct ParseError {
     pub line: usize,
@@ -21,44 +24,47 @@ pub struct ParseError {
 }
 pub type ParseResult<T> = Result<T, ParseError>;
 impl ::std::fmt::Display for ParseError {
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter)
-     -> ::std::result::Result<(), ::std::fmt::Error> {
-        try!(write ! (
-             fmt , "error at {}:{}: expected " , self . line , self . column
-             ));
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(fmt, "error at {}:{}: expected ", self.line, self.column)?;
         if self.expected.len() == 0 {
-            try!(write ! ( fmt , "EOF" ));
+            write!(fmt, "EOF")?;
         } else if self.expected.len() == 1 {
-            try!(write ! (
-                 fmt , "`{}`" , escape_default (
-                 self . expected . iter (  ) . next (  ) . unwrap (  ) ) ));
+            write!(
+                fmt,
+                "`{}`",
+                escape_default(self.expected.iter().next().unwrap())
+            )?;
         } else {
             let mut iter = self.expected.iter();
-            try!(write ! (
-                 fmt , "one of `{}`" , escape_default (
-                 iter . next (  ) . unwrap (  ) ) ));
+            write!(fmt, "one of `{}`", escape_default(iter.next().unwrap()))?;
             for elem in iter {
-                try!(write ! ( fmt , ", `{}`" , escape_default ( elem ) ));
+                write!(fmt, ", `{}`", escape_default(elem))?;
             }
         }
         Ok(())
     }
 }
 impl ::std::error::Error for ParseError {
-    fn description(&self) -> &str { "parse error" }
+    fn description(&self) -> &str {
+        "parse error"
+    }
 }
-fn slice_eq(input: &str, state: &mut ParseState, pos: usize, m: &'static str)
- -> RuleResult<()> {
+fn slice_eq(input: &str, state: &mut ParseState, pos: usize, m: &'static str) -> RuleResult<()> {
     #![inline]
     #![allow(dead_code)]
     let l = m.len();
-    if input.len() >= pos + l &&
-           &input.as_bytes()[pos..pos + l] == m.as_bytes() {
+    if input.len() >= pos + l && &input.as_bytes()[pos..pos + l] == m.as_bytes() {
         Matched(pos + l, ())
-    } else { state.mark_failure(pos, m) }
+    } else {
+        state.mark_failure(pos, m)
+    }
 }
-fn slice_eq_case_insensitive(input: &str, state: &mut ParseState, pos: usize,
-                             m: &'static str) -> RuleResult<()> {
+fn slice_eq_case_insensitive(
+    input: &str,
+    state: &mut ParseState,
+    pos: usize,
+    m: &'static str,
+) -> RuleResult<()> {
     #![inline]
     #![allow(dead_code)]
     let mut used = 0usize;
@@ -66,1231 +72,5090 @@ fn slice_eq_case_insensitive(input: &str, state: &mut ParseState, pos: usize,
     for m_char_upper in m.chars().flat_map(|x| x.to_uppercase()) {
         used += m_char_upper.len_utf8();
         let input_char_result = input_iter.next();
-        if input_char_result.is_none() ||
-               input_char_result.unwrap() != m_char_upper {
+        if input_char_result.is_none() || input_char_result.unwrap() != m_char_upper {
             return state.mark_failure(pos, m);
         }
     }
     Matched(pos + used, ())
 }
-fn any_char(input: &str, state: &mut ParseState, pos: usize)
- -> RuleResult<()> {
+fn any_char(input: &str, state: &mut ParseState, pos: usize) -> RuleResult<()> {
     #![inline]
     #![allow(dead_code)]
     if input.len() > pos {
         let (_, next) = char_range_at(input, pos);
         Matched(next, ())
-    } else { state.mark_failure(pos, "<character>") }
+    } else {
+        state.mark_failure(pos, "<character>")
+    }
 }
 fn pos_to_line(input: &str, pos: usize) -> (usize, usize) {
-    let mut remaining = pos;
-    let mut lineno: usize = 1;
-    for line in input.lines() {
-        let line_length = line.len() + 1;
-        if remaining < line_length { return (lineno, remaining + 1); }
-        remaining -= line_length;
-        lineno += 1;
+    let before = &input[..pos];
+    let line = before.as_bytes().iter().filter(|&&c| c == b'\n').count() + 1;
+    let col = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, col)
+}
+impl<'input> ParseState<'input> {
+    fn mark_failure(&mut self, pos: usize, expected: &'static str) -> RuleResult<()> {
+        if self.suppress_fail == 0 {
+            if pos > self.max_err_pos {
+                self.max_err_pos = pos;
+                self.expected.clear();
+            }
+            if pos == self.max_err_pos {
+                self.expected.insert(expected);
+            }
+        }
+        Failed
     }
-    return (lineno, remaining + 1);
 }
 struct ParseState<'input> {
     max_err_pos: usize,
+    suppress_fail: usize,
     expected: ::std::collections::HashSet<&'static str>,
     _phantom: ::std::marker::PhantomData<&'input ()>,
 }
-impl <'input> ParseState<'input> {
+impl<'input> ParseState<'input> {
     fn new() -> ParseState<'input> {
-        ParseState{max_err_pos: 0,
-                   expected: ::std::collections::HashSet::new(),
-                   _phantom: ::std::marker::PhantomData,}
-    }
-    fn mark_failure(&mut self, pos: usize, expected: &'static str)
-     -> RuleResult<()> {
-        if pos > self.max_err_pos {
-            self.max_err_pos = pos;
-            self.expected.clear();
+        ParseState {
+            max_err_pos: 0,
+            suppress_fail: 0,
+            expected: ::std::collections::HashSet::new(),
+            _phantom: ::std::marker::PhantomData,
         }
-        if pos == self.max_err_pos { self.expected.insert(expected); }
-        Failed
     }
 }
-fn parse_script<'input>(input: &'input str, state: &mut ParseState<'input>,
-                        pos: usize) -> RuleResult<Script> {
-    {
-        let start_pos = pos;
-        {
-            let seq_res = parse_selector_section(input, state, pos);
-            match seq_res {
-                Matched(pos, s) => {
-                    {
-                        let seq_res = parse_mode_separator(input, state, pos);
-                        match seq_res {
-                            Matched(pos, m) => {
-                                {
-                                    let seq_res =
-                                        parse_action_section(input, state,
-                                                             pos);
-                                    match seq_res {
-                                        Matched(pos, a) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            Script{selector:
-                                                                       s,
-                                                                   mode: m,
-                                                                   action: a,}
-                                                        })
-                                            }
-                                        }
-                                        Failed => Failed,
-                                    }
+
+fn __parse_script<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Script> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_selector_section(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, s) => {
+                let __seq_res = __parse_mode_separator(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, m) => {
+                        let __seq_res = __parse_action_section(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, a) => Matched(__pos, {
+                                Script {
+                                    selector: s,
+                                    mode: m,
+                                    action: a,
                                 }
-                            }
+                            }),
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_space_separator<'input>(input: &'input str,
-                                 state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<()> {
+
+fn __parse_jsonpath_script<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Script> {
+    #![allow(non_snake_case, unused)]
     {
-        let mut repeat_pos = pos;
-        let mut repeat_value = vec!();
-        loop  {
-            let pos = repeat_pos;
-            let step_res =
-                if input.len() > pos {
-                    let (ch, next) = char_range_at(input, pos);
-                    match ch {
-                        ' ' | '\t' => Matched(next, ()),
-                        _ => state.mark_failure(pos, "[ \t]"),
+        let __seq_res = __parse_jsonpath(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, s) => {
+                let __seq_res = __parse_mode_separator(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, m) => {
+                        let __seq_res = __parse_action_section(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, a) => Matched(__pos, {
+                                Script {
+                                    selector: s,
+                                    mode: m,
+                                    action: a,
+                                }
+                            }),
+                            Failed => Failed,
+                        }
                     }
-                } else { state.mark_failure(pos, "[ \t]") };
-            match step_res {
-                Matched(newpos, value) => {
-                    repeat_pos = newpos;
-                    repeat_value.push(value);
+                    Failed => Failed,
                 }
-                Failed => { break ; }
             }
+            Failed => Failed,
         }
-        if repeat_value.len() >= 1usize {
-            Matched(repeat_pos, ())
-        } else { Failed }
     }
 }
-fn parse_ws<'input>(input: &'input str, state: &mut ParseState<'input>,
-                    pos: usize) -> RuleResult<()> {
+
+fn __parse_space_separator<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
     {
-        let mut repeat_pos = pos;
-        loop  {
-            let pos = repeat_pos;
-            let step_res =
-                if input.len() > pos {
-                    let (ch, next) = char_range_at(input, pos);
-                    match ch {
-                        ' ' | '\t' => Matched(next, ()),
-                        _ => state.mark_failure(pos, "[ \t]"),
-                    }
-                } else { state.mark_failure(pos, "[ \t]") };
-            match step_res {
-                Matched(newpos, value) => { repeat_pos = newpos; }
-                Failed => { break ; }
+        let mut __repeat_pos = __pos;
+        let mut __repeat_value = vec![];
+        loop {
+            let __pos = __repeat_pos;
+            let __step_res = if __input.len() > __pos {
+                let (__ch, __next) = char_range_at(__input, __pos);
+                match __ch {
+                    ' ' | '\t' => Matched(__next, ()),
+                    _ => __state.mark_failure(__pos, "[ \t]"),
+                }
+            } else {
+                __state.mark_failure(__pos, "[ \t]")
+            };
+            match __step_res {
+                Matched(__newpos, __value) => {
+                    __repeat_pos = __newpos;
+                    __repeat_value.push(__value);
+                }
+                Failed => {
+                    break;
+                }
+            }
+        }
+        if __repeat_value.len() >= 1 {
+            Matched(__repeat_pos, ())
+        } else {
+            Failed
+        }
+    }
+}
+
+fn __parse_ws<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let mut __repeat_pos = __pos;
+        loop {
+            let __pos = __repeat_pos;
+            let __step_res = if __input.len() > __pos {
+                let (__ch, __next) = char_range_at(__input, __pos);
+                match __ch {
+                    ' ' | '\t' => Matched(__next, ()),
+                    _ => __state.mark_failure(__pos, "[ \t]"),
+                }
+            } else {
+                __state.mark_failure(__pos, "[ \t]")
+            };
+            match __step_res {
+                Matched(__newpos, __value) => {
+                    __repeat_pos = __newpos;
+                }
+                Failed => {
+                    break;
+                }
             }
         }
-        Matched(repeat_pos, ())
+        Matched(__repeat_pos, ())
     }
 }
-fn parse_ident<'input>(input: &'input str, state: &mut ParseState<'input>,
-                       pos: usize) -> RuleResult<String> {
+
+fn __parse_ident<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res =
-                if input.len() > pos {
-                    let (ch, next) = char_range_at(input, pos);
-                    match ch {
-                        'a' ...'z' | 'A' ...'Z' | '_' => Matched(next, ()),
-                        _ => state.mark_failure(pos, "[a-zA-Z_]"),
+        let __seq_res = {
+            let str_start = __pos;
+            match {
+                let __seq_res = if __input.len() > __pos {
+                    let (__ch, __next) = char_range_at(__input, __pos);
+                    match __ch {
+                        'a'...'z' | 'A'...'Z' | '_' => Matched(__next, ()),
+                        _ => __state.mark_failure(__pos, "[a-zA-Z_]"),
                     }
-                } else { state.mark_failure(pos, "[a-zA-Z_]") };
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res =
-                            {
-                                let mut repeat_pos = pos;
-                                loop  {
-                                    let pos = repeat_pos;
-                                    let step_res =
-                                        if input.len() > pos {
-                                            let (ch, next) =
-                                                char_range_at(input, pos);
-                                            match ch {
-                                                'a' ...'z' | 'A' ...'Z' | '0'
-                                                ...'9' | '_' | '-' =>
-                                                Matched(next, ()),
-                                                _ =>
-                                                state.mark_failure(pos,
-                                                                   "[a-zA-Z0-9_-]"),
-                                            }
-                                        } else {
-                                            state.mark_failure(pos,
-                                                               "[a-zA-Z0-9_-]")
-                                        };
-                                    match step_res {
-                                        Matched(newpos, value) => {
-                                            repeat_pos = newpos;
-                                        }
-                                        Failed => { break ; }
-                                    }
+                } else {
+                    __state.mark_failure(__pos, "[a-zA-Z_]")
+                };
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let mut __repeat_pos = __pos;
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = __parse_ident_rest(__input, __state, __pos);
+                            match __step_res {
+                                Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
                                 }
-                                Matched(repeat_pos, ())
-                            };
-                        match seq_res {
-                            Matched(pos, _) => {
-                                {
-                                    let match_str = &input[start_pos..pos];
-                                    Matched(pos, { match_str.to_string() })
+                                Failed => {
+                                    break;
                                 }
                             }
-                            Failed => Failed,
                         }
+                        Matched(__repeat_pos, ())
                     }
+                    Failed => Failed,
                 }
+            } {
+                Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
                 Failed => Failed,
             }
+        };
+        match __seq_res {
+            Matched(__pos, i) => Matched(__pos, { i.to_string() }),
+            Failed => Failed,
         }
     }
 }
-fn parse_s_string<'input>(input: &'input str, state: &mut ParseState<'input>,
-                          pos: usize) -> RuleResult<String> {
+
+fn __parse_ident_rest<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = slice_eq(input, state, pos, "\'");
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res =
-                            {
-                                let mut repeat_pos = pos;
-                                let mut repeat_value = vec!();
-                                loop  {
-                                    let pos = repeat_pos;
-                                    let step_res =
-                                        parse_s_char(input, state, pos);
-                                    match step_res {
-                                        Matched(newpos, value) => {
-                                            repeat_pos = newpos;
-                                            repeat_value.push(value);
-                                        }
-                                        Failed => { break ; }
-                                    }
-                                }
-                                Matched(repeat_pos, repeat_value)
-                            };
-                        match seq_res {
-                            Matched(pos, s) => {
-                                {
-                                    let seq_res =
-                                        slice_eq(input, state, pos, "\'");
-                                    match seq_res {
-                                        Matched(pos, _) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            s.into_iter().collect()
-                                                        })
-                                            }
-                                        }
-                                        Failed => Failed,
-                                    }
-                                }
+        let __choice_res = if __input.len() > __pos {
+            let (__ch, __next) = char_range_at(__input, __pos);
+            match __ch {
+                'a'...'z' | 'A'...'Z' | '0'...'9' | '_' => Matched(__next, ()),
+                _ => __state.mark_failure(__pos, "[a-zA-Z0-9_]"),
+            }
+        } else {
+            __state.mark_failure(__pos, "[a-zA-Z0-9_]")
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = slice_eq(__input, __state, __pos, "-");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        __state.suppress_fail += 1;
+                        let __assert_res = slice_eq(__input, __state, __pos, ">");
+                        __state.suppress_fail -= 1;
+                        match __assert_res {
+                            Failed => Matched(__pos, ()),
+                            Matched(..) => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_s_string<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "'");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = __parse_s_char(__input, __state, __pos);
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
                             }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, s) => {
+                        let __seq_res = slice_eq(__input, __state, __pos, "'");
+                        match __seq_res {
+                            Matched(__pos, _) => Matched(__pos, { s.into_iter().collect() }),
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_d_string<'input>(input: &'input str, state: &mut ParseState<'input>,
-                          pos: usize) -> RuleResult<String> {
+
+fn __parse_d_string<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = slice_eq(input, state, pos, "\"");
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res =
-                            {
-                                let mut repeat_pos = pos;
-                                let mut repeat_value = vec!();
-                                loop  {
-                                    let pos = repeat_pos;
-                                    let step_res =
-                                        parse_d_char(input, state, pos);
-                                    match step_res {
-                                        Matched(newpos, value) => {
-                                            repeat_pos = newpos;
-                                            repeat_value.push(value);
-                                        }
-                                        Failed => { break ; }
-                                    }
-                                }
-                                Matched(repeat_pos, repeat_value)
-                            };
-                        match seq_res {
-                            Matched(pos, s) => {
-                                {
-                                    let seq_res =
-                                        slice_eq(input, state, pos, "\"");
-                                    match seq_res {
-                                        Matched(pos, _) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            s.into_iter().collect()
-                                                        })
-                                            }
-                                        }
-                                        Failed => Failed,
-                                    }
-                                }
+        let __seq_res = slice_eq(__input, __state, __pos, "\"");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = __parse_d_char(__input, __state, __pos);
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
                             }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, s) => {
+                        let __seq_res = slice_eq(__input, __state, __pos, "\"");
+                        match __seq_res {
+                            Matched(__pos, _) => Matched(__pos, { s.into_iter().collect() }),
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_s_char<'input>(input: &'input str, state: &mut ParseState<'input>,
-                        pos: usize) -> RuleResult<char> {
+
+fn __parse_s_char<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res =
-                {
-                    let assert_res = slice_eq(input, state, pos, "\'");
-                    match assert_res {
-                        Failed => Matched(pos, ()),
+        let __choice_res = __parse_string_escape(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = {
+                    __state.suppress_fail += 1;
+                    let __assert_res = slice_eq(__input, __state, __pos, "'");
+                    __state.suppress_fail -= 1;
+                    match __assert_res {
+                        Failed => Matched(__pos, ()),
                         Matched(..) => Failed,
                     }
                 };
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let match_str = &input[start_pos..pos];
-                        Matched(pos, { match_str.chars().next().unwrap() })
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            let str_start = __pos;
+                            match any_char(__input, __state, __pos) {
+                                Matched(__newpos, _) => {
+                                    Matched(__newpos, &__input[str_start..__newpos])
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, c) => Matched(__pos, { c.chars().next().unwrap() }),
+                            Failed => Failed,
+                        }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
         }
     }
 }
-fn parse_d_char<'input>(input: &'input str, state: &mut ParseState<'input>,
-                        pos: usize) -> RuleResult<char> {
+
+fn __parse_d_char<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res =
-                {
-                    let assert_res = slice_eq(input, state, pos, "\"");
-                    match assert_res {
-                        Failed => Matched(pos, ()),
+        let __choice_res = __parse_string_escape(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = {
+                    __state.suppress_fail += 1;
+                    let __assert_res = slice_eq(__input, __state, __pos, "\"");
+                    __state.suppress_fail -= 1;
+                    match __assert_res {
+                        Failed => Matched(__pos, ()),
                         Matched(..) => Failed,
                     }
                 };
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let match_str = &input[start_pos..pos];
-                        Matched(pos, { match_str.chars().next().unwrap() })
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            let str_start = __pos;
+                            match any_char(__input, __state, __pos) {
+                                Matched(__newpos, _) => {
+                                    Matched(__newpos, &__input[str_start..__newpos])
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, c) => Matched(__pos, { c.chars().next().unwrap() }),
+                            Failed => Failed,
+                        }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
         }
     }
 }
-fn parse_int<'input>(input: &'input str, state: &mut ParseState<'input>,
-                     pos: usize) -> RuleResult<i64> {
+
+fn __parse_string_escape<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res =
-            {
-                let start_pos = pos;
-                {
-                    let seq_res = slice_eq(input, state, pos, "0");
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let match_str = &input[start_pos..pos];
-                                Matched(pos, { 0 + 0 })
-                            }
+        let __seq_res = slice_eq(__input, __state, __pos, "\\");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let str_start = __pos;
+                    match if __input.len() > __pos {
+                        let (__ch, __next) = char_range_at(__input, __pos);
+                        match __ch {
+                            't' | 'n' | 'r' | '0' | '\'' | '"' | '\\' => Matched(__next, ()),
+                            _ => __state.mark_failure(__pos, "[tnr0'\"\\]"),
                         }
+                    } else {
+                        __state.mark_failure(__pos, "[tnr0'\"\\]")
+                    } {
+                        Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
                         Failed => Failed,
                     }
+                };
+                match __seq_res {
+                    Matched(__pos, c) => Matched(__pos, {
+                        match c {
+                            "t" => '\t',
+                            "n" => '\n',
+                            "r" => '\r',
+                            "0" => '\0',
+                            _ => c.chars().next().unwrap(),
+                        }
+                    }),
+                    Failed => Failed,
                 }
-            };
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_int<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<i64> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "0");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { 0 + 0 }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
             Failed => {
-                let start_pos = pos;
-                {
-                    let seq_res =
-                        if input.len() > pos {
-                            let (ch, next) = char_range_at(input, pos);
-                            match ch {
-                                '1' ...'9' => Matched(next, ()),
-                                _ => state.mark_failure(pos, "[1-9]"),
-                            }
-                        } else { state.mark_failure(pos, "[1-9]") };
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res =
-                                    {
-                                        let mut repeat_pos = pos;
-                                        loop  {
-                                            let pos = repeat_pos;
-                                            let step_res =
-                                                if input.len() > pos {
-                                                    let (ch, next) =
-                                                        char_range_at(input,
-                                                                      pos);
-                                                    match ch {
-                                                        '0' ...'9' =>
-                                                        Matched(next, ()),
-                                                        _ =>
-                                                        state.mark_failure(pos,
-                                                                           "[0-9]"),
-                                                    }
-                                                } else {
-                                                    state.mark_failure(pos,
-                                                                       "[0-9]")
-                                                };
-                                            match step_res {
-                                                Matched(newpos, value) => {
-                                                    repeat_pos = newpos;
-                                                }
-                                                Failed => { break ; }
-                                            }
+                let __seq_res = {
+                    let str_start = __pos;
+                    match {
+                        let __seq_res = if __input.len() > __pos {
+                            let (__ch, __next) = char_range_at(__input, __pos);
+                            match __ch {
+                                '1'...'9' => Matched(__next, ()),
+                                _ => __state.mark_failure(__pos, "[1-9]"),
+                            }
+                        } else {
+                            __state.mark_failure(__pos, "[1-9]")
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let mut __repeat_pos = __pos;
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __step_res = if __input.len() > __pos {
+                                        let (__ch, __next) = char_range_at(__input, __pos);
+                                        match __ch {
+                                            '0'...'9' => Matched(__next, ()),
+                                            _ => __state.mark_failure(__pos, "[0-9]"),
                                         }
-                                        Matched(repeat_pos, ())
+                                    } else {
+                                        __state.mark_failure(__pos, "[0-9]")
                                     };
-                                match seq_res {
-                                    Matched(pos, _) => {
-                                        {
-                                            let match_str =
-                                                &input[start_pos..pos];
-                                            Matched(pos,
-                                                    {
-                                                        match_str.parse().unwrap()
-                                                    })
+                                    match __step_res {
+                                        Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                        }
+                                        Failed => {
+                                            break;
                                         }
                                     }
-                                    Failed => Failed,
                                 }
+                                Matched(__repeat_pos, ())
                             }
+                            Failed => Failed,
                         }
+                    } {
+                        Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
                         Failed => Failed,
                     }
+                };
+                match __seq_res {
+                    Matched(__pos, i) => match { i.parse().map_err(|_| "<i64>") } {
+                        Ok(res) => Matched(__pos, res),
+                        Err(expected) => {
+                            __state.mark_failure(__pos, expected);
+                            Failed
+                        }
+                    },
+                    Failed => Failed,
                 }
             }
         }
     }
 }
-fn parse_indexer<'input>(input: &'input str, state: &mut ParseState<'input>,
-                         pos: usize) -> RuleResult<()> {
-    slice_eq(input, state, pos, ".")
+
+fn __parse_indexer<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    slice_eq(__input, __state, __pos, ".")
+}
+
+fn __parse_eacher<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    slice_eq(__input, __state, __pos, "->")
 }
-fn parse_eacher<'input>(input: &'input str, state: &mut ParseState<'input>,
-                        pos: usize) -> RuleResult<()> {
-    slice_eq(input, state, pos, "->")
+
+fn __parse_descender<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    slice_eq(__input, __state, __pos, "..")
 }
-fn parse_sint<'input>(input: &'input str, state: &mut ParseState<'input>,
-                      pos: usize) -> RuleResult<i64> {
+
+fn __parse_sint<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<i64> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res =
-            {
-                let start_pos = pos;
-                {
-                    let seq_res = slice_eq(input, state, pos, "-");
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res = parse_int(input, state, pos);
-                                match seq_res {
-                                    Matched(pos, v) => {
-                                        {
-                                            let match_str =
-                                                &input[start_pos..pos];
-                                            Matched(pos, { -v })
-                                        }
-                                    }
-                                    Failed => Failed,
-                                }
-                            }
-                        }
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "-");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_int(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, v) => Matched(__pos, { -v }),
                         Failed => Failed,
                     }
                 }
-            };
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => parse_int(input, state, pos),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => __parse_int(__input, __state, __pos),
         }
     }
 }
-fn parse_action_literal<'input>(input: &'input str,
-                                state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<ActionExpr> {
+
+fn __parse_action_literal<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res =
-            {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_int(input, state, pos);
-                    match seq_res {
-                        Matched(pos, i) => {
-                            {
-                                let match_str = &input[start_pos..pos];
-                                Matched(pos, { ActionExpr::Integer(i) })
-                            }
-                        }
-                        Failed => Failed,
-                    }
-                }
-            };
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => {
-                let choice_res =
-                    {
-                        let start_pos = pos;
-                        {
-                            let seq_res = parse_s_string(input, state, pos);
-                            match seq_res {
-                                Matched(pos, s) => {
-                                    {
-                                        let match_str =
-                                            &input[start_pos..pos];
-                                        Matched(pos,
-                                                { ActionExpr::String(s) })
+        let __choice_res = {
+            let __seq_res = {
+                let str_start = __pos;
+                match {
+                    let __seq_res = {
+                        let __choice_res = slice_eq(__input, __state, __pos, "0");
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __seq_res = if __input.len() > __pos {
+                                    let (__ch, __next) = char_range_at(__input, __pos);
+                                    match __ch {
+                                        '1'...'9' => Matched(__next, ()),
+                                        _ => __state.mark_failure(__pos, "[1-9]"),
+                                    }
+                                } else {
+                                    __state.mark_failure(__pos, "[1-9]")
+                                };
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let mut __repeat_pos = __pos;
+                                        loop {
+                                            let __pos = __repeat_pos;
+                                            let __step_res = if __input.len() > __pos {
+                                                let (__ch, __next) = char_range_at(__input, __pos);
+                                                match __ch {
+                                                    '0'...'9' => Matched(__next, ()),
+                                                    _ => __state.mark_failure(__pos, "[0-9]"),
+                                                }
+                                            } else {
+                                                __state.mark_failure(__pos, "[0-9]")
+                                            };
+                                            match __step_res {
+                                                Matched(__newpos, __value) => {
+                                                    __repeat_pos = __newpos;
+                                                }
+                                                Failed => {
+                                                    break;
+                                                }
+                                            }
+                                        }
+                                        Matched(__repeat_pos, ())
                                     }
+                                    Failed => Failed,
                                 }
-                                Failed => Failed,
                             }
                         }
                     };
-                match choice_res {
-                    Matched(pos, value) => Matched(pos, value),
-                    Failed => {
-                        let choice_res =
-                            {
-                                let start_pos = pos;
-                                {
-                                    let seq_res =
-                                        parse_d_string(input, state, pos);
-                                    match seq_res {
-                                        Matched(pos, d) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            ActionExpr::String(d)
-                                                        })
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = match {
+                                let __seq_res = slice_eq(__input, __state, __pos, ".");
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let mut __repeat_pos = __pos;
+                                        let mut __repeat_value = vec![];
+                                        loop {
+                                            let __pos = __repeat_pos;
+                                            let __step_res = if __input.len() > __pos {
+                                                let (__ch, __next) = char_range_at(__input, __pos);
+                                                match __ch {
+                                                    '0'...'9' => Matched(__next, ()),
+                                                    _ => __state.mark_failure(__pos, "[0-9]"),
+                                                }
+                                            } else {
+                                                __state.mark_failure(__pos, "[0-9]")
+                                            };
+                                            match __step_res {
+                                                Matched(__newpos, __value) => {
+                                                    __repeat_pos = __newpos;
+                                                    __repeat_value.push(__value);
+                                                }
+                                                Failed => {
+                                                    break;
+                                                }
                                             }
                                         }
-                                        Failed => Failed,
+                                        if __repeat_value.len() >= 1 {
+                                            Matched(__repeat_pos, ())
+                                        } else {
+                                            Failed
+                                        }
                                     }
+                                    Failed => Failed,
                                 }
+                            } {
+                                Matched(__newpos, _) => Matched(__newpos, ()),
+                                Failed => Matched(__pos, ()),
                             };
-                        match choice_res {
-                            Matched(pos, value) => Matched(pos, value),
-                            Failed => {
-                                let start_pos = pos;
-                                {
-                                    let seq_res =
-                                        parse_ident(input, state, pos);
-                                    match seq_res {
-                                        Matched(pos, v) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            ActionExpr::Variable(v)
-                                                        })
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    match {
+                                        let __seq_res = if __input.len() > __pos {
+                                            let (__ch, __next) = char_range_at(__input, __pos);
+                                            match __ch {
+                                                'e' | 'E' => Matched(__next, ()),
+                                                _ => __state.mark_failure(__pos, "[eE]"),
+                                            }
+                                        } else {
+                                            __state.mark_failure(__pos, "[eE]")
+                                        };
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let __seq_res = match if __input.len() > __pos {
+                                                    let (__ch, __next) =
+                                                        char_range_at(__input, __pos);
+                                                    match __ch {
+                                                        '+' | '-' => Matched(__next, ()),
+                                                        _ => __state.mark_failure(__pos, "[+-]"),
+                                                    }
+                                                } else {
+                                                    __state.mark_failure(__pos, "[+-]")
+                                                } {
+                                                    Matched(__newpos, _) => Matched(__newpos, ()),
+                                                    Failed => Matched(__pos, ()),
+                                                };
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        let mut __repeat_pos = __pos;
+                                                        let mut __repeat_value = vec![];
+                                                        loop {
+                                                            let __pos = __repeat_pos;
+                                                            let __step_res = if __input.len()
+                                                                > __pos
+                                                            {
+                                                                let (__ch, __next) =
+                                                                    char_range_at(__input, __pos);
+                                                                match __ch {
+                                                                    '0'...'9' => {
+                                                                        Matched(__next, ())
+                                                                    }
+                                                                    _ => __state.mark_failure(
+                                                                        __pos, "[0-9]",
+                                                                    ),
+                                                                }
+                                                            } else {
+                                                                __state.mark_failure(__pos, "[0-9]")
+                                                            };
+                                                            match __step_res {
+                                                                Matched(__newpos, __value) => {
+                                                                    __repeat_pos = __newpos;
+                                                                    __repeat_value.push(__value);
+                                                                }
+                                                                Failed => {
+                                                                    break;
+                                                                }
+                                                            }
+                                                        }
+                                                        if __repeat_value.len() >= 1 {
+                                                            Matched(__repeat_pos, ())
+                                                        } else {
+                                                            Failed
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
                                             }
+                                            Failed => Failed,
                                         }
-                                        Failed => Failed,
+                                    } {
+                                        Matched(__newpos, _) => Matched(__newpos, ()),
+                                        Failed => Matched(__pos, ()),
                                     }
                                 }
+                                Failed => Failed,
                             }
                         }
+                        Failed => Failed,
                     }
+                } {
+                    Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
+                    Failed => Failed,
                 }
-            }
-        }
-    }
-}
-fn parse_action_object_index<'input>(input: &'input str,
-                                     state: &mut ParseState<'input>,
-                                     pos: usize) -> RuleResult<ActionExpr> {
-    {
-        let start_pos = pos;
-        {
-            let seq_res = parse_indexer(input, state, pos);
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res =
-                            parse_object_index_name(input, state, pos);
-                        match seq_res {
-                            Matched(pos, n) => {
-                                {
-                                    let match_str = &input[start_pos..pos];
-                                    Matched(pos,
-                                            { ActionExpr::ObjectIndex(n) })
-                                }
-                            }
-                            Failed => Failed,
+            };
+            match __seq_res {
+                Matched(__pos, n) => {
+                    match {
+                        match n.parse::<i64>() {
+                            Ok(i) => Ok(ActionExpr::Integer(i)),
+                            Err(_) => match n.parse::<f64>() {
+                                Ok(f) if f.is_finite() => Ok(ActionExpr::Float(f)),
+                                _ => Err("<f64>"),
+                            },
+                        }
+                    } {
+                        Ok(res) => Matched(__pos, res),
+                        Err(expected) => {
+                            __state.mark_failure(__pos, expected);
+                            Failed
                         }
                     }
                 }
                 Failed => Failed,
             }
-        }
-    }
-}
-fn parse_action_array_index<'input>(input: &'input str,
-                                    state: &mut ParseState<'input>,
-                                    pos: usize) -> RuleResult<ActionExpr> {
-    {
-        let start_pos = pos;
-        {
-            let seq_res = parse_indexer(input, state, pos);
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res = parse_l_square(input, state, pos);
-                        match seq_res {
-                            Matched(pos, _) => {
-                                {
-                                    let seq_res =
-                                        parse_sint(input, state, pos);
-                                    match seq_res {
-                                        Matched(pos, i) => {
-                                            {
-                                                let seq_res =
-                                                    parse_r_square(input,
-                                                                   state,
-                                                                   pos);
-                                                match seq_res {
-                                                    Matched(pos, _) => {
-                                                        {
-                                                            let match_str =
-                                                                &input[start_pos..pos];
-                                                            Matched(pos,
-                                                                    {
-                                                                        ActionExpr::ArrayIndex(i)
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = __parse_s_string(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, s) => Matched(__pos, { ActionExpr::String(s) }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = __parse_d_string(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, d) => Matched(__pos, { ActionExpr::String(d) }),
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "true");
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res = {
+                                                __state.suppress_fail += 1;
+                                                let __assert_res =
+                                                    __parse_ident_rest(__input, __state, __pos);
+                                                __state.suppress_fail -= 1;
+                                                match __assert_res {
+                                                    Failed => Matched(__pos, ()),
+                                                    Matched(..) => Failed,
+                                                }
+                                            };
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    Matched(__pos, { ActionExpr::Bool(true) })
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __choice_res = {
+                                            let __seq_res =
+                                                slice_eq(__input, __state, __pos, "false");
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res = {
+                                                        __state.suppress_fail += 1;
+                                                        let __assert_res = __parse_ident_rest(
+                                                            __input, __state, __pos,
+                                                        );
+                                                        __state.suppress_fail -= 1;
+                                                        match __assert_res {
+                                                            Failed => Matched(__pos, ()),
+                                                            Matched(..) => Failed,
+                                                        }
+                                                    };
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => Matched(__pos, {
+                                                            ActionExpr::Bool(false)
+                                                        }),
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        };
+                                        match __choice_res {
+                                            Matched(__pos, __value) => Matched(__pos, __value),
+                                            Failed => {
+                                                let __choice_res = {
+                                                    let __seq_res =
+                                                        slice_eq(__input, __state, __pos, "null");
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            let __seq_res = {
+                                                                __state.suppress_fail += 1;
+                                                                let __assert_res =
+                                                                    __parse_ident_rest(
+                                                                        __input, __state, __pos,
+                                                                    );
+                                                                __state.suppress_fail -= 1;
+                                                                match __assert_res {
+                                                                    Failed => Matched(__pos, ()),
+                                                                    Matched(..) => Failed,
+                                                                }
+                                                            };
+                                                            match __seq_res {
+                                                                Matched(__pos, _) => {
+                                                                    Matched(__pos, {
+                                                                        ActionExpr::Null
                                                                     })
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                };
+                                                match __choice_res {
+                                                    Matched(__pos, __value) => {
+                                                        Matched(__pos, __value)
+                                                    }
+                                                    Failed => {
+                                                        let __seq_res =
+                                                            __parse_ident(__input, __state, __pos);
+                                                        match __seq_res {
+                                                            Matched(__pos, v) => Matched(__pos, {
+                                                                ActionExpr::Variable(v)
+                                                            }),
+                                                            Failed => Failed,
                                                         }
                                                     }
-                                                    Failed => Failed,
                                                 }
                                             }
                                         }
-                                        Failed => Failed,
                                     }
                                 }
                             }
-                            Failed => Failed,
                         }
                     }
                 }
-                Failed => Failed,
             }
         }
     }
 }
-fn parse_object_index_name<'input>(input: &'input str,
-                                   state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<String> {
+
+fn __parse_action_object_index<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res = parse_ident(input, state, pos);
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => {
-                let choice_res = parse_s_string(input, state, pos);
-                match choice_res {
-                    Matched(pos, value) => Matched(pos, value),
-                    Failed => parse_d_string(input, state, pos),
+        let __seq_res = __parse_indexer(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_object_index_name(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, n) => Matched(__pos, { ActionExpr::ObjectIndex(n) }),
+                    Failed => Failed,
                 }
             }
-        }
-    }
-}
-fn parse_l_square<'input>(input: &'input str, state: &mut ParseState<'input>,
-                          pos: usize) -> RuleResult<()> {
-    {
-        let seq_res = slice_eq(input, state, pos, "[");
-        match seq_res {
-            Matched(pos, _) => { parse_ws(input, state, pos) }
-            Failed => Failed,
-        }
-    }
-}
-fn parse_r_square<'input>(input: &'input str, state: &mut ParseState<'input>,
-                          pos: usize) -> RuleResult<()> {
-    {
-        let seq_res = parse_ws(input, state, pos);
-        match seq_res {
-            Matched(pos, _) => { slice_eq(input, state, pos, "]") }
             Failed => Failed,
         }
     }
 }
-fn parse_colon<'input>(input: &'input str, state: &mut ParseState<'input>,
-                       pos: usize) -> RuleResult<()> {
+
+fn __parse_action_array_index<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
     {
-        let seq_res = parse_ws(input, state, pos);
-        match seq_res {
-            Matched(pos, _) => {
-                {
-                    let seq_res = slice_eq(input, state, pos, ":");
-                    match seq_res {
-                        Matched(pos, _) => { parse_ws(input, state, pos) }
-                        Failed => Failed,
+        let __seq_res = __parse_indexer(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_l_square(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_sint(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, i) => {
+                                let __seq_res = __parse_r_square(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        Matched(__pos, { ActionExpr::ArrayIndex(i) })
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
                     }
+                    Failed => Failed,
                 }
             }
             Failed => Failed,
         }
     }
 }
-fn parse_mode_separator<'input>(input: &'input str,
-                                state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<ActionMode> {
+
+fn __parse_object_index_name<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res =
-            {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_ws(input, state, pos);
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res =
-                                    slice_eq(input, state, pos, "@");
-                                match seq_res {
-                                    Matched(pos, _) => {
-                                        {
-                                            let seq_res =
-                                                parse_ws(input, state, pos);
-                                            match seq_res {
-                                                Matched(pos, _) => {
-                                                    {
-                                                        let match_str =
-                                                            &input[start_pos..pos];
-                                                        Matched(pos,
-                                                                {
-                                                                    ActionMode::ForSelf
-                                                                })
-                                                    }
-                                                }
-                                                Failed => Failed,
-                                            }
-                                        }
-                                    }
-                                    Failed => Failed,
-                                }
+        let __choice_res = __parse_ident(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => __parse_quoted_string(__input, __state, __pos),
+        }
+    }
+}
+
+fn __parse_quoted_string<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_s_string(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => __parse_d_string(__input, __state, __pos),
+        }
+    }
+}
+
+fn __parse_l_square<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "[");
+        match __seq_res {
+            Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_r_square<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_ws(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => slice_eq(__input, __state, __pos, "]"),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_colon<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_ws(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = slice_eq(__input, __state, __pos, ":");
+                match __seq_res {
+                    Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_comma<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_ws(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = slice_eq(__input, __state, __pos, ",");
+                match __seq_res {
+                    Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_mode_separator<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionMode> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_ws(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = slice_eq(__input, __state, __pos, "@");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { ActionMode::ForSelf }),
+                                Failed => Failed,
                             }
                         }
                         Failed => Failed,
                     }
                 }
-            };
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
             Failed => {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_ws(input, state, pos);
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res =
-                                    slice_eq(input, state, pos, "%");
-                                match seq_res {
-                                    Matched(pos, _) => {
-                                        {
-                                            let seq_res =
-                                                parse_ws(input, state, pos);
-                                            match seq_res {
-                                                Matched(pos, _) => {
-                                                    {
-                                                        let match_str =
-                                                            &input[start_pos..pos];
-                                                        Matched(pos,
-                                                                {
-                                                                    ActionMode::ForEach
-                                                                })
-                                                    }
-                                                }
-                                                Failed => Failed,
-                                            }
-                                        }
-                                    }
+                let __seq_res = __parse_ws(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = slice_eq(__input, __state, __pos, "%");
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_ws(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => Matched(__pos, { ActionMode::ForEach }),
                                     Failed => Failed,
                                 }
                             }
+                            Failed => Failed,
                         }
-                        Failed => Failed,
                     }
+                    Failed => Failed,
                 }
             }
         }
     }
 }
-fn parse_action_separator<'input>(input: &'input str,
-                                  state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<()> {
+
+fn __parse_action_separator<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
     {
-        let seq_res = parse_ws(input, state, pos);
-        match seq_res {
-            Matched(pos, _) => {
-                {
-                    let seq_res = slice_eq(input, state, pos, ";");
-                    match seq_res {
-                        Matched(pos, _) => { parse_ws(input, state, pos) }
-                        Failed => Failed,
-                    }
+        let __seq_res = __parse_ws(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = slice_eq(__input, __state, __pos, ";");
+                match __seq_res {
+                    Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                    Failed => Failed,
                 }
             }
             Failed => Failed,
         }
     }
 }
-fn parse_selector_section<'input>(input: &'input str,
-                                  state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Vec<Selector>> {
+
+fn __parse_selector_section<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Selector>> {
+    #![allow(non_snake_case, unused)]
     {
-        let mut repeat_pos = pos;
-        let mut repeat_value = vec!();
-        loop  {
-            let pos = repeat_pos;
-            let step_res = parse_selector(input, state, pos);
-            match step_res {
-                Matched(newpos, value) => {
-                    repeat_pos = newpos;
-                    repeat_value.push(value);
+        let __choice_res = __parse_pointer_section(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let mut __repeat_pos = __pos;
+                let mut __repeat_value = vec![];
+                loop {
+                    let __pos = __repeat_pos;
+                    let __step_res = __parse_selector(__input, __state, __pos);
+                    match __step_res {
+                        Matched(__newpos, __value) => {
+                            __repeat_pos = __newpos;
+                            __repeat_value.push(__value);
+                        }
+                        Failed => {
+                            break;
+                        }
+                    }
                 }
-                Failed => { break ; }
+                Matched(__repeat_pos, __repeat_value)
             }
         }
-        Matched(repeat_pos, repeat_value)
     }
 }
-fn parse_selector<'input>(input: &'input str, state: &mut ParseState<'input>,
-                          pos: usize) -> RuleResult<Selector> {
+
+fn __parse_pointer_section<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Selector>> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res =
-            {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_indexer(input, state, pos);
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res = parse_sop(input, state, pos);
-                                match seq_res {
-                                    Matched(pos, s) => {
-                                        {
-                                            let match_str =
-                                                &input[start_pos..pos];
-                                            Matched(pos,
-                                                    { Selector::ForSelf(s) })
-                                        }
-                                    }
-                                    Failed => Failed,
+        let __seq_res = {
+            let mut __repeat_pos = __pos;
+            let mut __repeat_value = vec![];
+            loop {
+                let __pos = __repeat_pos;
+                let __step_res = __parse_pointer_token(__input, __state, __pos);
+                match __step_res {
+                    Matched(__newpos, __value) => {
+                        __repeat_pos = __newpos;
+                        __repeat_value.push(__value);
+                    }
+                    Failed => {
+                        break;
+                    }
+                }
+            }
+            if __repeat_value.len() >= 1 {
+                Matched(__repeat_pos, __repeat_value)
+            } else {
+                Failed
+            }
+        };
+        match __seq_res {
+            Matched(__pos, t) => Matched(__pos, {
+                t.into_iter()
+                    .map(|t| Selector::ForSelf(Sop::PointerToken(t)))
+                    .collect()
+            }),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_pointer_token<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "/");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let str_start = __pos;
+                    match {
+                        let mut __repeat_pos = __pos;
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = if __input.len() > __pos {
+                                let (__ch, __next) = char_range_at(__input, __pos);
+                                match __ch {
+                                    '/' | ' ' | '\t' => __state.mark_failure(__pos, "[^/ \t]"),
+                                    _ => Matched(__next, ()),
+                                }
+                            } else {
+                                __state.mark_failure(__pos, "[^/ \t]")
+                            };
+                            match __step_res {
+                                Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
+                                }
+                                Failed => {
+                                    break;
                                 }
                             }
                         }
+                        Matched(__repeat_pos, ())
+                    } {
+                        Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
                         Failed => Failed,
                     }
+                };
+                match __seq_res {
+                    Matched(__pos, t) => {
+                        Matched(__pos, { t.replace("~1", "/").replace("~0", "~") })
+                    }
+                    Failed => Failed,
                 }
-            };
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_selector<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Selector> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_descender(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_sop(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, s) => Matched(__pos, { Selector::Descendant(s) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
             Failed => {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_eacher(input, state, pos);
-                    match seq_res {
-                        Matched(pos, _) => {
-                            {
-                                let seq_res = parse_sop(input, state, pos);
-                                match seq_res {
-                                    Matched(pos, s) => {
-                                        {
-                                            let match_str =
-                                                &input[start_pos..pos];
-                                            Matched(pos,
-                                                    { Selector::ForEach(s) })
-                                        }
-                                    }
+                let __choice_res = {
+                    let __seq_res = __parse_indexer(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_sop(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, s) => Matched(__pos, { Selector::ForSelf(s) }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = __parse_eacher(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_sop(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, s) => Matched(__pos, { Selector::ForEach(s) }),
                                     Failed => Failed,
                                 }
                             }
+                            Failed => Failed,
                         }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_plain_sop(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, s) => {
+                    let __seq_res = slice_eq(__input, __state, __pos, "?");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { Sop::Optional(Box::new(s)) }),
                         Failed => Failed,
                     }
                 }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => __parse_plain_sop(__input, __state, __pos),
+        }
+    }
+}
+
+fn __parse_plain_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_filter_sop(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = __parse_array_sop(__input, __state, __pos);
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => __parse_object_sop(__input, __state, __pos),
+                }
             }
         }
     }
 }
-fn parse_sop<'input>(input: &'input str, state: &mut ParseState<'input>,
-                     pos: usize) -> RuleResult<Sop> {
+
+fn __parse_array_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res = parse_array_sop(input, state, pos);
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => parse_object_sop(input, state, pos),
+        let __choice_res = __parse_array_index_sop(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = __parse_array_union_sop(__input, __state, __pos);
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => __parse_array_slice_sop(__input, __state, __pos),
+                }
+            }
         }
     }
 }
-fn parse_array_sop<'input>(input: &'input str, state: &mut ParseState<'input>,
-                           pos: usize) -> RuleResult<Sop> {
+
+fn __parse_array_index_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res = parse_array_index_sop(input, state, pos);
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => parse_array_slice_sop(input, state, pos),
+        let __seq_res = __parse_l_square(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_sint(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, index) => {
+                        let __seq_res = __parse_r_square(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => Matched(__pos, { Sop::ArrayIndex(index) }),
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
         }
     }
 }
-fn parse_array_index_sop<'input>(input: &'input str,
-                                 state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Sop> {
+
+fn __parse_array_union_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = parse_l_square(input, state, pos);
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res = parse_sint(input, state, pos);
-                        match seq_res {
-                            Matched(pos, index) => {
-                                {
-                                    let seq_res =
-                                        parse_r_square(input, state, pos);
-                                    match seq_res {
-                                        Matched(pos, _) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            Sop::ArrayIndex(index)
-                                                        })
-                                            }
-                                        }
-                                        Failed => Failed,
-                                    }
-                                }
+        let __seq_res = __parse_l_square(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __pos = if __repeat_value.len() > 0 {
+                            let __sep_res = __parse_comma(__input, __state, __pos);
+                            match __sep_res {
+                                Matched(__newpos, _) => __newpos,
+                                Failed => break,
                             }
+                        } else {
+                            __pos
+                        };
+                        let __step_res = __parse_sint(__input, __state, __pos);
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    if __repeat_value.len() >= 1 {
+                        Matched(__repeat_pos, __repeat_value)
+                    } else {
+                        Failed
+                    }
+                };
+                match __seq_res {
+                    Matched(__pos, i) => {
+                        let __seq_res = __parse_r_square(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => Matched(__pos, { Sop::ArrayUnion(i) }),
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_array_slice_sop<'input>(input: &'input str,
-                                 state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Sop> {
+
+fn __parse_array_slice_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = parse_l_square(input, state, pos);
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let seq_res =
-                            match parse_sint(input, state, pos) {
-                                Matched(newpos, value) => {
-                                    Matched(newpos, Some(value))
-                                }
-                                Failed => { Matched(pos, None) }
-                            };
-                        match seq_res {
-                            Matched(pos, start) => {
-                                {
-                                    let seq_res =
-                                        parse_colon(input, state, pos);
-                                    match seq_res {
-                                        Matched(pos, _) => {
-                                            {
-                                                let seq_res =
-                                                    match parse_sint(input,
-                                                                     state,
-                                                                     pos) {
-                                                        Matched(newpos, value)
-                                                        => {
-                                                            Matched(newpos,
-                                                                    Some(value))
-                                                        }
-                                                        Failed => {
-                                                            Matched(pos, None)
-                                                        }
-                                                    };
-                                                match seq_res {
-                                                    Matched(pos, end) => {
-                                                        {
-                                                            let seq_res =
-                                                                parse_r_square(input,
-                                                                               state,
-                                                                               pos);
-                                                            match seq_res {
-                                                                Matched(pos,
-                                                                        _) =>
-                                                                {
-                                                                    {
-                                                                        let match_str =
-                                                                            &input[start_pos..pos];
-                                                                        Matched(pos,
-                                                                                {
-                                                                                    Sop::ArraySlice(ArraySlice::new(start,
-                                                                                                                    end,
-                                                                                                                    None))
-                                                                                })
-                                                                    }
-                                                                }
-                                                                Failed =>
-                                                                Failed,
-                                                            }
-                                                        }
-                                                    }
+        let __seq_res = __parse_l_square(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = match __parse_sint(__input, __state, __pos) {
+                    Matched(__newpos, __value) => Matched(__newpos, Some(__value)),
+                    Failed => Matched(__pos, None),
+                };
+                match __seq_res {
+                    Matched(__pos, start) => {
+                        let __seq_res = __parse_colon(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = match __parse_sint(__input, __state, __pos) {
+                                    Matched(__newpos, __value) => Matched(__newpos, Some(__value)),
+                                    Failed => Matched(__pos, None),
+                                };
+                                match __seq_res {
+                                    Matched(__pos, end) => {
+                                        let __seq_res =
+                                            match __parse_slice_step(__input, __state, __pos) {
+                                                Matched(__newpos, __value) => {
+                                                    Matched(__newpos, Some(__value))
+                                                }
+                                                Failed => Matched(__pos, None),
+                                            };
+                                        match __seq_res {
+                                            Matched(__pos, step) => {
+                                                let __seq_res =
+                                                    __parse_r_square(__input, __state, __pos);
+                                                match __seq_res {
+                                                    Matched(__pos, _) => Matched(__pos, {
+                                                        Sop::ArraySlice(ArraySlice::new(
+                                                            start,
+                                                            end,
+                                                            step.and_then(|s| s),
+                                                        ))
+                                                    }),
                                                     Failed => Failed,
                                                 }
                                             }
+                                            Failed => Failed,
                                         }
-                                        Failed => Failed,
                                     }
+                                    Failed => Failed,
                                 }
                             }
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_object_sop<'input>(input: &'input str,
-                            state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Sop> {
+
+fn __parse_slice_step<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Option<i64>> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res = parse_wildcard_object_sop(input, state, pos);
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => parse_member_object_sop(input, state, pos),
+        let __seq_res = __parse_colon(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = match __parse_sint(__input, __state, __pos) {
+                    Matched(__newpos, __value) => Matched(__newpos, Some(__value)),
+                    Failed => Matched(__pos, None),
+                };
+                match __seq_res {
+                    Matched(__pos, s) => Matched(__pos, { s }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
         }
     }
 }
-fn parse_wildcard_object_sop<'input>(input: &'input str,
-                                     state: &mut ParseState<'input>,
-                                     pos: usize) -> RuleResult<Sop> {
+
+fn __parse_object_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = slice_eq(input, state, pos, "*");
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let match_str = &input[start_pos..pos];
-                        Matched(pos, { Sop::Object(ObjectIndexer::Wildcard) })
-                    }
+        let __choice_res = __parse_object_union_sop(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = __parse_wildcard_object_sop(__input, __state, __pos);
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => __parse_member_object_sop(__input, __state, __pos),
                 }
-                Failed => Failed,
             }
         }
     }
 }
-fn parse_member_object_sop<'input>(input: &'input str,
-                                   state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Sop> {
+
+fn __parse_object_union_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
     {
-        let start_pos = pos;
-        {
-            let seq_res = parse_object_index_name(input, state, pos);
-            match seq_res {
-                Matched(pos, _) => {
-                    {
-                        let match_str = &input[start_pos..pos];
-                        Matched(pos,
-                                {
-                                    Sop::Object(ObjectIndexer::Exact(match_str.to_string()))
-                                })
+        let __seq_res = __parse_l_square(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __pos = if __repeat_value.len() > 0 {
+                            let __sep_res = __parse_comma(__input, __state, __pos);
+                            match __sep_res {
+                                Matched(__newpos, _) => __newpos,
+                                Failed => break,
+                            }
+                        } else {
+                            __pos
+                        };
+                        let __step_res = __parse_quoted_string(__input, __state, __pos);
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    if __repeat_value.len() >= 1 {
+                        Matched(__repeat_pos, __repeat_value)
+                    } else {
+                        Failed
+                    }
+                };
+                match __seq_res {
+                    Matched(__pos, k) => {
+                        let __seq_res = __parse_r_square(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => Matched(__pos, { Sop::ObjectUnion(k) }),
+                            Failed => Failed,
+                        }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_action_section<'input>(input: &'input str,
-                                state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Vec<Function>> {
-    {
-        let mut repeat_pos = pos;
-        let mut repeat_value = vec!();
-        loop  {
-            let pos = repeat_pos;
-            let pos =
-                if repeat_value.len() > 0 {
-                    let sep_res = parse_action_separator(input, state, pos);
-                    match sep_res {
-                        Matched(newpos, _) => { newpos }
-                        Failed => break ,
-                    }
-                } else { pos };
-            let step_res = parse_action_func(input, state, pos);
-            match step_res {
-                Matched(newpos, value) => {
-                    repeat_pos = newpos;
-                    repeat_value.push(value);
-                }
-                Failed => { break ; }
-            }
-        }
-        Matched(repeat_pos, repeat_value)
-    }
-}
-fn parse_action_func<'input>(input: &'input str,
-                             state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Function> {
-    {
-        let start_pos = pos;
-        {
-            let seq_res = parse_ident(input, state, pos);
-            match seq_res {
-                Matched(pos, n) => {
-                    {
-                        let seq_res =
-                            parse_space_separator(input, state, pos);
-                        match seq_res {
-                            Matched(pos, _) => {
-                                {
-                                    let seq_res =
-                                        parse_action_expr_list(input, state,
-                                                               pos);
-                                    match seq_res {
-                                        Matched(pos, p) => {
-                                            {
-                                                let match_str =
-                                                    &input[start_pos..pos];
-                                                Matched(pos,
-                                                        {
-                                                            Function{name: n,
-                                                                     args: p,}
-                                                        })
+
+fn __parse_wildcard_object_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "*");
+        match __seq_res {
+            Matched(__pos, _) => Matched(__pos, { Sop::Object(ObjectIndexer::Wildcard) }),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_member_object_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_object_index_name(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, n) => Matched(__pos, { Sop::Object(ObjectIndexer::Exact(n)) }),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_filter_sop<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_l_square(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = slice_eq(__input, __state, __pos, "?(");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_ws(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_filter(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, f) => {
+                                        let __seq_res = __parse_ws(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let __seq_res =
+                                                    slice_eq(__input, __state, __pos, ")");
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        let __seq_res = __parse_r_square(
+                                                            __input, __state, __pos,
+                                                        );
+                                                        match __seq_res {
+                                                            Matched(__pos, _) => {
+                                                                Matched(__pos, { Sop::Filter(f) })
+                                                            }
+                                                            Failed => Failed,
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
                                             }
+                                            Failed => Failed,
                                         }
-                                        Failed => Failed,
                                     }
+                                    Failed => Failed,
                                 }
                             }
                             Failed => Failed,
                         }
                     }
+                    Failed => Failed,
                 }
-                Failed => Failed,
             }
+            Failed => Failed,
         }
     }
 }
-fn parse_action_expr_list<'input>(input: &'input str,
-                                  state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<Vec<ActionExpr>> {
+
+fn __parse_filter<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
     {
-        let mut repeat_pos = pos;
-        let mut repeat_value = vec!();
-        loop  {
-            let pos = repeat_pos;
-            let pos =
-                if repeat_value.len() > 0 {
-                    let sep_res = parse_space_separator(input, state, pos);
-                    match sep_res {
-                        Matched(newpos, _) => { newpos }
-                        Failed => break ,
+        fn __infix_parse<'input>(
+            __min_prec: i32,
+            __input: &'input str,
+            __state: &mut ParseState<'input>,
+            __pos: usize,
+        ) -> RuleResult<Filter> {
+            if let Matched(__pos, mut __infix_result) =
+                __parse_filter_unary(__input, __state, __pos)
+            {
+                let mut __repeat_pos = __pos;
+                loop {
+                    let __pos = __repeat_pos;
+                    if 0i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "||");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(1i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { Filter::Or(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
+                    }
+                    if 1i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "&&");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(2i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { Filter::And(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
                     }
-                } else { pos };
-            let step_res = parse_action_expr(input, state, pos);
-            match step_res {
-                Matched(newpos, value) => {
-                    repeat_pos = newpos;
-                    repeat_value.push(value);
+                    break;
                 }
-                Failed => { break ; }
+                Matched(__repeat_pos, __infix_result)
+            } else {
+                Failed
             }
         }
-        Matched(repeat_pos, repeat_value)
+        __infix_parse(0, __input, __state, __pos)
     }
 }
-fn parse_action_expr<'input>(input: &'input str,
-                             state: &mut ParseState<'input>, pos: usize)
- -> RuleResult<ActionExpr> {
+
+fn __parse_filter_unary<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
     {
-        let choice_res = parse_action_literal(input, state, pos);
-        match choice_res {
-            Matched(pos, value) => Matched(pos, value),
-            Failed => {
-                let choice_res = parse_action_object_index(input, state, pos);
-                match choice_res {
-                    Matched(pos, value) => Matched(pos, value),
-                    Failed => parse_action_array_index(input, state, pos),
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "!");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_ws(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_filter_unary(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, f) => Matched(__pos, { Filter::Not(Box::new(f)) }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
                 }
+                Failed => Failed,
             }
-        }
-    }
-}
-pub fn script<'input>(input: &'input str) -> ParseResult<Script> {
-    let mut state = ParseState::new();
-    match parse_script(input, &mut state, 0) {
-        Matched(pos, value) => { if pos == input.len() { return Ok(value) } }
-        _ => { }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "(");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_filter(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, f) => {
+                                            let __seq_res = __parse_ws(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res =
+                                                        slice_eq(__input, __state, __pos, ")");
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => Matched(__pos, { f }),
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = __parse_filter_comparison(__input, __state, __pos);
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __seq_res = __parse_filter_path(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, p) => Matched(__pos, { Filter::Exists(p) }),
+                                    Failed => Failed,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_filter_comparison<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_filter_operand(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, l) => {
+                let __seq_res = __parse_ws(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_cmp_op(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, op) => {
+                                let __seq_res = __parse_ws(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res =
+                                            __parse_filter_operand(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, r) => {
+                                                Matched(__pos, { Filter::Compare(op, l, r) })
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_cmp_op<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<CmpOp> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "==");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { CmpOp::Eq }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "!=");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { CmpOp::Ne }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = slice_eq(__input, __state, __pos, "<=");
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { CmpOp::Le }),
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "<");
+                                    match __seq_res {
+                                        Matched(__pos, _) => Matched(__pos, { CmpOp::Lt }),
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __choice_res = {
+                                            let __seq_res = slice_eq(__input, __state, __pos, ">=");
+                                            match __seq_res {
+                                                Matched(__pos, _) => Matched(__pos, { CmpOp::Ge }),
+                                                Failed => Failed,
+                                            }
+                                        };
+                                        match __choice_res {
+                                            Matched(__pos, __value) => Matched(__pos, __value),
+                                            Failed => {
+                                                let __seq_res =
+                                                    slice_eq(__input, __state, __pos, ">");
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        Matched(__pos, { CmpOp::Gt })
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_filter_operand<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<FilterOperand> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_filter_path(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, p) => Matched(__pos, { FilterOperand::Path(p) }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = __parse_json_literal(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, v) => Matched(__pos, { FilterOperand::Literal(v) }),
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_filter_path<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<PathStep>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "@");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = {
+                        let mut __repeat_pos = __pos;
+                        let mut __repeat_value = vec![];
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = __parse_path_step(__input, __state, __pos);
+                            match __step_res {
+                                Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
+                                    __repeat_value.push(__value);
+                                }
+                                Failed => {
+                                    break;
+                                }
+                            }
+                        }
+                        Matched(__repeat_pos, __repeat_value)
+                    };
+                    match __seq_res {
+                        Matched(__pos, p) => Matched(__pos, { p }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let mut __repeat_pos = __pos;
+                let mut __repeat_value = vec![];
+                loop {
+                    let __pos = __repeat_pos;
+                    let __step_res = __parse_path_step(__input, __state, __pos);
+                    match __step_res {
+                        Matched(__newpos, __value) => {
+                            __repeat_pos = __newpos;
+                            __repeat_value.push(__value);
+                        }
+                        Failed => {
+                            break;
+                        }
+                    }
+                }
+                if __repeat_value.len() >= 1 {
+                    Matched(__repeat_pos, __repeat_value)
+                } else {
+                    Failed
+                }
+            }
+        }
+    }
+}
+
+fn __parse_path_step<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<PathStep> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_indexer(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_l_square(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_sint(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, i) => {
+                                    let __seq_res = __parse_r_square(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => Matched(__pos, { PathStep::Index(i) }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = __parse_indexer(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_object_index_name(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, n) => Matched(__pos, { PathStep::Key(n) }),
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_json_literal<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Value> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = {
+                let str_start = __pos;
+                match {
+                    let __seq_res = match slice_eq(__input, __state, __pos, "-") {
+                        Matched(__newpos, _) => Matched(__newpos, ()),
+                        Failed => Matched(__pos, ()),
+                    };
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = {
+                                let __choice_res = slice_eq(__input, __state, __pos, "0");
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __seq_res = if __input.len() > __pos {
+                                            let (__ch, __next) = char_range_at(__input, __pos);
+                                            match __ch {
+                                                '1'...'9' => Matched(__next, ()),
+                                                _ => __state.mark_failure(__pos, "[1-9]"),
+                                            }
+                                        } else {
+                                            __state.mark_failure(__pos, "[1-9]")
+                                        };
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let mut __repeat_pos = __pos;
+                                                loop {
+                                                    let __pos = __repeat_pos;
+                                                    let __step_res = if __input.len() > __pos {
+                                                        let (__ch, __next) =
+                                                            char_range_at(__input, __pos);
+                                                        match __ch {
+                                                            '0'...'9' => Matched(__next, ()),
+                                                            _ => {
+                                                                __state.mark_failure(__pos, "[0-9]")
+                                                            }
+                                                        }
+                                                    } else {
+                                                        __state.mark_failure(__pos, "[0-9]")
+                                                    };
+                                                    match __step_res {
+                                                        Matched(__newpos, __value) => {
+                                                            __repeat_pos = __newpos;
+                                                        }
+                                                        Failed => {
+                                                            break;
+                                                        }
+                                                    }
+                                                }
+                                                Matched(__repeat_pos, ())
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                }
+                            };
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = match {
+                                        let __seq_res = slice_eq(__input, __state, __pos, ".");
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let mut __repeat_pos = __pos;
+                                                let mut __repeat_value = vec![];
+                                                loop {
+                                                    let __pos = __repeat_pos;
+                                                    let __step_res = if __input.len() > __pos {
+                                                        let (__ch, __next) =
+                                                            char_range_at(__input, __pos);
+                                                        match __ch {
+                                                            '0'...'9' => Matched(__next, ()),
+                                                            _ => {
+                                                                __state.mark_failure(__pos, "[0-9]")
+                                                            }
+                                                        }
+                                                    } else {
+                                                        __state.mark_failure(__pos, "[0-9]")
+                                                    };
+                                                    match __step_res {
+                                                        Matched(__newpos, __value) => {
+                                                            __repeat_pos = __newpos;
+                                                            __repeat_value.push(__value);
+                                                        }
+                                                        Failed => {
+                                                            break;
+                                                        }
+                                                    }
+                                                }
+                                                if __repeat_value.len() >= 1 {
+                                                    Matched(__repeat_pos, ())
+                                                } else {
+                                                    Failed
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    } {
+                                        Matched(__newpos, _) => Matched(__newpos, ()),
+                                        Failed => Matched(__pos, ()),
+                                    };
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            match {
+                                                let __seq_res = if __input.len() > __pos {
+                                                    let (__ch, __next) =
+                                                        char_range_at(__input, __pos);
+                                                    match __ch {
+                                                        'e' | 'E' => Matched(__next, ()),
+                                                        _ => __state.mark_failure(__pos, "[eE]"),
+                                                    }
+                                                } else {
+                                                    __state.mark_failure(__pos, "[eE]")
+                                                };
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        let __seq_res = match if __input.len()
+                                                            > __pos
+                                                        {
+                                                            let (__ch, __next) =
+                                                                char_range_at(__input, __pos);
+                                                            match __ch {
+                                                                '+' | '-' => Matched(__next, ()),
+                                                                _ => __state
+                                                                    .mark_failure(__pos, "[+-]"),
+                                                            }
+                                                        } else {
+                                                            __state.mark_failure(__pos, "[+-]")
+                                                        } {
+                                                            Matched(__newpos, _) => {
+                                                                Matched(__newpos, ())
+                                                            }
+                                                            Failed => Matched(__pos, ()),
+                                                        };
+                                                        match __seq_res {
+                                                            Matched(__pos, _) => {
+                                                                let mut __repeat_pos = __pos;
+                                                                let mut __repeat_value = vec![];
+                                                                loop {
+                                                                    let __pos = __repeat_pos;
+                                                                    let __step_res = if __input
+                                                                        .len()
+                                                                        > __pos
+                                                                    {
+                                                                        let (__ch, __next) =
+                                                                            char_range_at(
+                                                                                __input, __pos,
+                                                                            );
+                                                                        match __ch {
+                                                                            '0'...'9' => {
+                                                                                Matched(__next, ())
+                                                                            }
+                                                                            _ => __state
+                                                                                .mark_failure(
+                                                                                    __pos, "[0-9]",
+                                                                                ),
+                                                                        }
+                                                                    } else {
+                                                                        __state.mark_failure(
+                                                                            __pos, "[0-9]",
+                                                                        )
+                                                                    };
+                                                                    match __step_res {
+                                                                        Matched(
+                                                                            __newpos,
+                                                                            __value,
+                                                                        ) => {
+                                                                            __repeat_pos = __newpos;
+                                                                            __repeat_value
+                                                                                .push(__value);
+                                                                        }
+                                                                        Failed => {
+                                                                            break;
+                                                                        }
+                                                                    }
+                                                                }
+                                                                if __repeat_value.len() >= 1 {
+                                                                    Matched(__repeat_pos, ())
+                                                                } else {
+                                                                    Failed
+                                                                }
+                                                            }
+                                                            Failed => Failed,
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            } {
+                                                Matched(__newpos, _) => Matched(__newpos, ()),
+                                                Failed => Matched(__pos, ()),
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                } {
+                    Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
+                    Failed => Failed,
+                }
+            };
+            match __seq_res {
+                Matched(__pos, n) => {
+                    match {
+                        match n.parse::<i64>() {
+                            Ok(i) => Ok(Value::from(i)),
+                            Err(_) => match n.parse::<f64>() {
+                                Ok(f) if f.is_finite() => Ok(Value::from(f)),
+                                _ => Err("<f64>"),
+                            },
+                        }
+                    } {
+                        Ok(res) => Matched(__pos, res),
+                        Err(expected) => {
+                            __state.mark_failure(__pos, expected);
+                            Failed
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = __parse_s_string(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, s) => Matched(__pos, { Value::String(s) }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = __parse_d_string(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, d) => Matched(__pos, { Value::String(d) }),
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "true");
+                                    match __seq_res {
+                                        Matched(__pos, _) => Matched(__pos, { Value::Bool(true) }),
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __choice_res = {
+                                            let __seq_res =
+                                                slice_eq(__input, __state, __pos, "false");
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    Matched(__pos, { Value::Bool(false) })
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        };
+                                        match __choice_res {
+                                            Matched(__pos, __value) => Matched(__pos, __value),
+                                            Failed => {
+                                                let __seq_res =
+                                                    slice_eq(__input, __state, __pos, "null");
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        Matched(__pos, { Value::Null })
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_action_section<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Statement>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let mut __repeat_pos = __pos;
+        let mut __repeat_value = vec![];
+        loop {
+            let __pos = __repeat_pos;
+            let __pos = if __repeat_value.len() > 0 {
+                let __sep_res = __parse_action_separator(__input, __state, __pos);
+                match __sep_res {
+                    Matched(__newpos, _) => __newpos,
+                    Failed => break,
+                }
+            } else {
+                __pos
+            };
+            let __step_res = __parse_action_statement(__input, __state, __pos);
+            match __step_res {
+                Matched(__newpos, __value) => {
+                    __repeat_pos = __newpos;
+                    __repeat_value.push(__value);
+                }
+                Failed => {
+                    break;
+                }
+            }
+        }
+        Matched(__repeat_pos, __repeat_value)
+    }
+}
+
+fn __parse_action_statement<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Statement> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_action_if(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = __parse_action_assign(__input, __state, __pos);
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = __parse_action_func(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, f) => Matched(__pos, { Statement::Call(f) }),
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_action_if<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Statement> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "if");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_space_separator(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_action_expr(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, c) => {
+                                let __seq_res = __parse_ws(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res =
+                                            __parse_action_block(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, t) => {
+                                                let __seq_res = match {
+                                                    let __seq_res =
+                                                        __parse_ws(__input, __state, __pos);
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            let __seq_res = slice_eq(
+                                                                __input, __state, __pos, "else",
+                                                            );
+                                                            match __seq_res {
+                                                                Matched(__pos, _) => {
+                                                                    let __seq_res = __parse_ws(
+                                                                        __input, __state, __pos,
+                                                                    );
+                                                                    match __seq_res {
+                                                                        Matched(__pos, _) => {
+                                                                            let __seq_res =
+                                                                                __parse_action_else(
+                                                                                    __input,
+                                                                                    __state, __pos,
+                                                                                );
+                                                                            match __seq_res {
+                                                                                Matched(
+                                                                                    __pos,
+                                                                                    e,
+                                                                                ) => Matched(
+                                                                                    __pos,
+                                                                                    { e },
+                                                                                ),
+                                                                                Failed => Failed,
+                                                                            }
+                                                                        }
+                                                                        Failed => Failed,
+                                                                    }
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                } {
+                                                    Matched(__newpos, __value) => {
+                                                        Matched(__newpos, Some(__value))
+                                                    }
+                                                    Failed => Matched(__pos, None),
+                                                };
+                                                match __seq_res {
+                                                    Matched(__pos, e) => Matched(__pos, {
+                                                        Statement::If(c, t, e.unwrap_or(Vec::new()))
+                                                    }),
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_else<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Statement>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_action_block(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = __parse_action_if(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, s) => Matched(__pos, { vec![s] }),
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_action_block<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Statement>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "{");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_ws(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_action_section(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, a) => {
+                                let __seq_res = __parse_ws(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res = slice_eq(__input, __state, __pos, "}");
+                                        match __seq_res {
+                                            Matched(__pos, _) => Matched(__pos, { a }),
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_assign<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Statement> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = {
+            __state.suppress_fail += 1;
+            let __assert_res = __parse_value_word(__input, __state, __pos);
+            __state.suppress_fail -= 1;
+            match __assert_res {
+                Failed => Matched(__pos, ()),
+                Matched(..) => Failed,
+            }
+        };
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_ident(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, n) => {
+                        let __seq_res = __parse_ws(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = slice_eq(__input, __state, __pos, "=");
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res = {
+                                            __state.suppress_fail += 1;
+                                            let __assert_res =
+                                                slice_eq(__input, __state, __pos, "=");
+                                            __state.suppress_fail -= 1;
+                                            match __assert_res {
+                                                Failed => Matched(__pos, ()),
+                                                Matched(..) => Failed,
+                                            }
+                                        };
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let __seq_res = __parse_ws(__input, __state, __pos);
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        let __seq_res = __parse_action_expr(
+                                                            __input, __state, __pos,
+                                                        );
+                                                        match __seq_res {
+                                                            Matched(__pos, e) => Matched(__pos, {
+                                                                Statement::Assign(n, e)
+                                                            }),
+                                                            Failed => Failed,
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_keyword<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = {
+            let __choice_res = slice_eq(__input, __state, __pos, "if");
+            match __choice_res {
+                Matched(__pos, __value) => Matched(__pos, __value),
+                Failed => slice_eq(__input, __state, __pos, "else"),
+            }
+        };
+        match __seq_res {
+            Matched(__pos, _) => {
+                __state.suppress_fail += 1;
+                let __assert_res = if __input.len() > __pos {
+                    let (__ch, __next) = char_range_at(__input, __pos);
+                    match __ch {
+                        'a'...'z' | 'A'...'Z' | '0'...'9' | '_' => Matched(__next, ()),
+                        _ => __state.mark_failure(__pos, "[a-zA-Z0-9_]"),
+                    }
+                } else {
+                    __state.mark_failure(__pos, "[a-zA-Z0-9_]")
+                };
+                __state.suppress_fail -= 1;
+                match __assert_res {
+                    Failed => Matched(__pos, ()),
+                    Matched(..) => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_value_word<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = {
+            let __choice_res = slice_eq(__input, __state, __pos, "true");
+            match __choice_res {
+                Matched(__pos, __value) => Matched(__pos, __value),
+                Failed => {
+                    let __choice_res = slice_eq(__input, __state, __pos, "false");
+                    match __choice_res {
+                        Matched(__pos, __value) => Matched(__pos, __value),
+                        Failed => slice_eq(__input, __state, __pos, "null"),
+                    }
+                }
+            }
+        };
+        match __seq_res {
+            Matched(__pos, _) => {
+                __state.suppress_fail += 1;
+                let __assert_res = __parse_ident_rest(__input, __state, __pos);
+                __state.suppress_fail -= 1;
+                match __assert_res {
+                    Failed => Matched(__pos, ()),
+                    Matched(..) => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_func<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Function> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = {
+            __state.suppress_fail += 1;
+            let __assert_res = __parse_keyword(__input, __state, __pos);
+            __state.suppress_fail -= 1;
+            match __assert_res {
+                Failed => Matched(__pos, ()),
+                Matched(..) => Failed,
+            }
+        };
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_ident(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, n) => {
+                        let __seq_res = __parse_space_separator(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_action_expr_list(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, p) => {
+                                        Matched(__pos, { Function { name: n, args: p } })
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_expr_list<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<ActionExpr>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let mut __repeat_pos = __pos;
+        let mut __repeat_value = vec![];
+        loop {
+            let __pos = __repeat_pos;
+            let __pos = if __repeat_value.len() > 0 {
+                let __sep_res = __parse_space_separator(__input, __state, __pos);
+                match __sep_res {
+                    Matched(__newpos, _) => __newpos,
+                    Failed => break,
+                }
+            } else {
+                __pos
+            };
+            let __step_res = __parse_action_expr(__input, __state, __pos);
+            match __step_res {
+                Matched(__newpos, __value) => {
+                    __repeat_pos = __newpos;
+                    __repeat_value.push(__value);
+                }
+                Failed => {
+                    break;
+                }
+            }
+        }
+        Matched(__repeat_pos, __repeat_value)
+    }
+}
+
+fn __parse_action_expr<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
+    {
+        fn __infix_parse<'input>(
+            __min_prec: i32,
+            __input: &'input str,
+            __state: &mut ParseState<'input>,
+            __pos: usize,
+        ) -> RuleResult<ActionExpr> {
+            if let Matched(__pos, mut __infix_result) =
+                __parse_action_comparison(__input, __state, __pos)
+            {
+                let mut __repeat_pos = __pos;
+                loop {
+                    let __pos = __repeat_pos;
+                    if 0i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "||");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(1i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { ActionExpr::Or(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
+                    }
+                    if 1i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "&&");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_ws(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(2i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { ActionExpr::And(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
+                    }
+                    break;
+                }
+                Matched(__repeat_pos, __infix_result)
+            } else {
+                Failed
+            }
+        }
+        __infix_parse(0, __input, __state, __pos)
+    }
+}
+
+fn __parse_action_comparison<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_action_sum(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, l) => {
+                let __seq_res = match {
+                    let __seq_res = __parse_ws(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_cmp_op(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, op) => {
+                                    let __seq_res = __parse_ws(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res =
+                                                __parse_action_sum(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, r) => Matched(__pos, { (op, r) }),
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                } {
+                    Matched(__newpos, __value) => Matched(__newpos, Some(__value)),
+                    Failed => Matched(__pos, None),
+                };
+                match __seq_res {
+                    Matched(__pos, r) => Matched(__pos, {
+                        match r {
+                            Some((op, r)) => ActionExpr::Compare(op, Box::new(l), Box::new(r)),
+                            None => l,
+                        }
+                    }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_sum<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_action_product(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, l) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = {
+                            let __seq_res = __parse_action_sum_op(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, o) => {
+                                    let __seq_res = __parse_action_product(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, e) => Matched(__pos, { (o, e) }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, r) => Matched(__pos, { fold_arith(l, r) }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_sum_op<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ArithOp> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_ws(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = slice_eq(__input, __state, __pos, "+");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { ArithOp::Add }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = __parse_space_separator(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = slice_eq(__input, __state, __pos, "-");
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_space_separator(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => Matched(__pos, { ArithOp::Sub }),
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_action_product<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = __parse_action_unary(__input, __state, __pos);
+        match __seq_res {
+            Matched(__pos, l) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = {
+                            let __seq_res = __parse_action_product_op(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, o) => {
+                                    let __seq_res = __parse_action_unary(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, e) => Matched(__pos, { (o, e) }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, r) => Matched(__pos, { fold_arith(l, r) }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_action_product_op<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ArithOp> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_ws(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = slice_eq(__input, __state, __pos, "*");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { ArithOp::Mul }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = __parse_ws(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = slice_eq(__input, __state, __pos, "/");
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_ws(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => Matched(__pos, { ArithOp::Div }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = __parse_ws(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = slice_eq(__input, __state, __pos, "%");
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res = __parse_ws(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, _) => Matched(__pos, { ArithOp::Mod }),
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_action_unary<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<ActionExpr> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "-");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_action_unary(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, e) => Matched(__pos, { ActionExpr::Neg(Box::new(e)) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "!");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_ws(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_action_unary(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, e) => {
+                                            Matched(__pos, { ActionExpr::Not(Box::new(e)) })
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = slice_eq(__input, __state, __pos, "(");
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_ws(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res =
+                                                __parse_action_expr(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, e) => {
+                                                    let __seq_res =
+                                                        __parse_ws(__input, __state, __pos);
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            let __seq_res = slice_eq(
+                                                                __input, __state, __pos, ")",
+                                                            );
+                                                            match __seq_res {
+                                                                Matched(__pos, _) => {
+                                                                    Matched(__pos, { e })
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = __parse_action_literal(__input, __state, __pos);
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __choice_res =
+                                            __parse_action_object_index(__input, __state, __pos);
+                                        match __choice_res {
+                                            Matched(__pos, __value) => Matched(__pos, __value),
+                                            Failed => {
+                                                __parse_action_array_index(__input, __state, __pos)
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_blank<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<()> {
+    #![allow(non_snake_case, unused)]
+    {
+        let mut __repeat_pos = __pos;
+        loop {
+            let __pos = __repeat_pos;
+            let __step_res = if __input.len() > __pos {
+                let (__ch, __next) = char_range_at(__input, __pos);
+                match __ch {
+                    ' ' | '\t' | '\n' | '\r' => Matched(__next, ()),
+                    _ => __state.mark_failure(__pos, "[ \t\n\r]"),
+                }
+            } else {
+                __state.mark_failure(__pos, "[ \t\n\r]")
+            };
+            match __step_res {
+                Matched(__newpos, __value) => {
+                    __repeat_pos = __newpos;
+                }
+                Failed => {
+                    break;
+                }
+            }
+        }
+        Matched(__repeat_pos, ())
+    }
+}
+
+fn __parse_jsonpath<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<Selector>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "$");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res =
+                                        __parse_jsonpath_segment(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, s) => Matched(__pos, { s }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, s) => Matched(__pos, { s }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_jsonpath_segment<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Selector> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "..");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_jsonpath_descendant_body(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, s) => Matched(__pos, { Selector::PathDescendant(s) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, ".");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_jsonpath_dot_body(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, s) => Matched(__pos, { Selector::PathChild(s) }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = __parse_jsonpath_bracket(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, s) => Matched(__pos, { Selector::PathChild(s) }),
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_dot_body<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "*");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { Sop::Object(ObjectIndexer::Wildcard) }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = __parse_jsonpath_member_name(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, n) => Matched(__pos, { Sop::Object(ObjectIndexer::Exact(n)) }),
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_descendant_body<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_jsonpath_bracket(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => __parse_jsonpath_dot_body(__input, __state, __pos),
+        }
+    }
+}
+
+fn __parse_jsonpath_member_name<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = {
+            let str_start = __pos;
+            match {
+                let __seq_res = if __input.len() > __pos {
+                    let (__ch, __next) = char_range_at(__input, __pos);
+                    match __ch {
+                        'a'...'z' | 'A'...'Z' | '_' | '\u{80}'...'\u{10ffff}' => {
+                            Matched(__next, ())
+                        }
+                        _ => __state.mark_failure(__pos, "[a-zA-Z_\u{80}-\u{10ffff}]"),
+                    }
+                } else {
+                    __state.mark_failure(__pos, "[a-zA-Z_\u{80}-\u{10ffff}]")
+                };
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let mut __repeat_pos = __pos;
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = if __input.len() > __pos {
+                                let (__ch, __next) = char_range_at(__input, __pos);
+                                match __ch {
+                                    'a'...'z'
+                                    | 'A'...'Z'
+                                    | '0'...'9'
+                                    | '_'
+                                    | '\u{80}'...'\u{10ffff}' => Matched(__next, ()),
+                                    _ => {
+                                        __state.mark_failure(__pos, "[a-zA-Z0-9_\u{80}-\u{10ffff}]")
+                                    }
+                                }
+                            } else {
+                                __state.mark_failure(__pos, "[a-zA-Z0-9_\u{80}-\u{10ffff}]")
+                            };
+                            match __step_res {
+                                Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
+                                }
+                                Failed => {
+                                    break;
+                                }
+                            }
+                        }
+                        Matched(__repeat_pos, ())
+                    }
+                    Failed => Failed,
+                }
+            } {
+                Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
+                Failed => Failed,
+            }
+        };
+        match __seq_res {
+            Matched(__pos, n) => Matched(__pos, { n.to_string() }),
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_jsonpath_bracket<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "[");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = __parse_blank(__input, __state, __pos);
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = vec![];
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __pos = if __repeat_value.len() > 0 {
+                                    let __sep_res = {
+                                        let __seq_res = __parse_blank(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, _) => {
+                                                let __seq_res =
+                                                    slice_eq(__input, __state, __pos, ",");
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        __parse_blank(__input, __state, __pos)
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    };
+                                    match __sep_res {
+                                        Matched(__newpos, _) => __newpos,
+                                        Failed => break,
+                                    }
+                                } else {
+                                    __pos
+                                };
+                                let __step_res = __parse_jsonpath_selector(__input, __state, __pos);
+                                match __step_res {
+                                    Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            if __repeat_value.len() >= 1 {
+                                Matched(__repeat_pos, __repeat_value)
+                            } else {
+                                Failed
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, s) => {
+                                let __seq_res = __parse_blank(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res = slice_eq(__input, __state, __pos, "]");
+                                        match __seq_res {
+                                            Matched(__pos, _) => Matched(__pos, { union_sop(s) }),
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_jsonpath_selector<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Sop> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "*");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { Sop::Object(ObjectIndexer::Wildcard) }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "?");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res =
+                                        __parse_jsonpath_filter(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, f) => Matched(__pos, { Sop::Filter(f) }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = match __parse_jsonpath_index(__input, __state, __pos) {
+                                Matched(__newpos, __value) => Matched(__newpos, Some(__value)),
+                                Failed => Matched(__pos, None),
+                            };
+                            match __seq_res {
+                                Matched(__pos, start) => {
+                                    let __seq_res = __parse_blank(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res = slice_eq(__input, __state, __pos, ":");
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res =
+                                                        __parse_blank(__input, __state, __pos);
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            let __seq_res =
+                                                                match __parse_jsonpath_index(
+                                                                    __input, __state, __pos,
+                                                                ) {
+                                                                    Matched(__newpos, __value) => {
+                                                                        Matched(
+                                                                            __newpos,
+                                                                            Some(__value),
+                                                                        )
+                                                                    }
+                                                                    Failed => Matched(__pos, None),
+                                                                };
+                                                            match __seq_res {
+                                                                Matched(__pos, end) => {
+                                                                    let __seq_res = match {
+                                                                        let __seq_res =
+                                                                            __parse_blank(
+                                                                                __input, __state,
+                                                                                __pos,
+                                                                            );
+                                                                        match __seq_res {
+                                                                            Matched(__pos, _) => {
+                                                                                let __seq_res =
+                                                                                    slice_eq(
+                                                                                        __input,
+                                                                                        __state,
+                                                                                        __pos, ":",
+                                                                                    );
+                                                                                match __seq_res {
+                                                                                    Matched(
+                                                                                        __pos,
+                                                                                        _,
+                                                                                    ) => {
+                                                                                        let __seq_res = __parse_blank ( __input , __state , __pos ) ;
+                                                                                        match __seq_res { Matched ( __pos , _ ) => { { let __seq_res = match __parse_jsonpath_index ( __input , __state , __pos ) { Matched ( __newpos , __value ) => { Matched ( __newpos , Some ( __value ) ) } , Failed => { Matched ( __pos , None ) } , } ; match __seq_res { Matched ( __pos , s ) => { Matched ( __pos , {  s  } ) } Failed => Failed , } } } Failed => Failed , }
+                                                                                    }
+                                                                                    Failed => {
+                                                                                        Failed
+                                                                                    }
+                                                                                }
+                                                                            }
+                                                                            Failed => Failed,
+                                                                        }
+                                                                    } {
+                                                                        Matched(
+                                                                            __newpos,
+                                                                            __value,
+                                                                        ) => Matched(
+                                                                            __newpos,
+                                                                            Some(__value),
+                                                                        ),
+                                                                        Failed => {
+                                                                            Matched(__pos, None)
+                                                                        }
+                                                                    };
+                                                                    match __seq_res {
+                                                                        Matched(__pos, step) => {
+                                                                            Matched(__pos, {
+                                                                                Sop::ArraySlice(ArraySlice::new(start, end, step.and_then(|s| s)))
+                                                                            })
+                                                                        }
+                                                                        Failed => Failed,
+                                                                    }
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = {
+                                    let __seq_res = __parse_jsonpath_index(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, i) => Matched(__pos, { Sop::ArrayIndex(i) }),
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __seq_res =
+                                            __parse_jsonpath_string(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, k) => Matched(__pos, {
+                                                Sop::Object(ObjectIndexer::Exact(k))
+                                            }),
+                                            Failed => Failed,
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_index<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<i64> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "0");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { 0 }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = {
+                    let str_start = __pos;
+                    match {
+                        let __seq_res = match slice_eq(__input, __state, __pos, "-") {
+                            Matched(__newpos, _) => Matched(__newpos, ()),
+                            Failed => Matched(__pos, ()),
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = if __input.len() > __pos {
+                                    let (__ch, __next) = char_range_at(__input, __pos);
+                                    match __ch {
+                                        '1'...'9' => Matched(__next, ()),
+                                        _ => __state.mark_failure(__pos, "[1-9]"),
+                                    }
+                                } else {
+                                    __state.mark_failure(__pos, "[1-9]")
+                                };
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let mut __repeat_pos = __pos;
+                                        loop {
+                                            let __pos = __repeat_pos;
+                                            let __step_res = if __input.len() > __pos {
+                                                let (__ch, __next) = char_range_at(__input, __pos);
+                                                match __ch {
+                                                    '0'...'9' => Matched(__next, ()),
+                                                    _ => __state.mark_failure(__pos, "[0-9]"),
+                                                }
+                                            } else {
+                                                __state.mark_failure(__pos, "[0-9]")
+                                            };
+                                            match __step_res {
+                                                Matched(__newpos, __value) => {
+                                                    __repeat_pos = __newpos;
+                                                }
+                                                Failed => {
+                                                    break;
+                                                }
+                                            }
+                                        }
+                                        Matched(__repeat_pos, ())
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    } {
+                        Matched(__newpos, _) => Matched(__newpos, &__input[str_start..__newpos]),
+                        Failed => Failed,
+                    }
+                };
+                match __seq_res {
+                    Matched(__pos, n) => {
+                        match {
+                            match n.parse::<i64>() {
+                                Ok(i) if i.abs() <= 9007199254740991 => Ok(i),
+                                _ => Err("<i-json>"),
+                            }
+                        } {
+                            Ok(res) => Matched(__pos, res),
+                            Err(expected) => {
+                                __state.mark_failure(__pos, expected);
+                                Failed
+                            }
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_filter<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
+    {
+        fn __infix_parse<'input>(
+            __min_prec: i32,
+            __input: &'input str,
+            __state: &mut ParseState<'input>,
+            __pos: usize,
+        ) -> RuleResult<Filter> {
+            if let Matched(__pos, mut __infix_result) =
+                __parse_jsonpath_filter_unary(__input, __state, __pos)
+            {
+                let mut __repeat_pos = __pos;
+                loop {
+                    let __pos = __repeat_pos;
+                    if 0i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "||");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_blank(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(1i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { Filter::Or(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
+                    }
+                    if 1i32 >= __min_prec {
+                        if let Matched(__pos, _) = {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "&&");
+                                    match __seq_res {
+                                        Matched(__pos, _) => __parse_blank(__input, __state, __pos),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            if let Matched(__pos, y) = __infix_parse(2i32, __input, __state, __pos)
+                            {
+                                let x = __infix_result;
+                                __infix_result = { Filter::And(Box::new(x), Box::new(y)) };
+                                __repeat_pos = __pos;
+                                continue;
+                            }
+                        }
+                    }
+                    break;
+                }
+                Matched(__repeat_pos, __infix_result)
+            } else {
+                Failed
+            }
+        }
+        __infix_parse(0, __input, __state, __pos)
+    }
+}
+
+fn __parse_jsonpath_filter_unary<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "!");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_blank(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_jsonpath_filter_test(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, f) => Matched(__pos, { Filter::Not(Box::new(f)) }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = __parse_jsonpath_filter_operand(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, l) => {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_cmp_op(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, op) => {
+                                            let __seq_res = __parse_blank(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res = __parse_jsonpath_filter_operand(
+                                                        __input, __state, __pos,
+                                                    );
+                                                    match __seq_res {
+                                                        Matched(__pos, r) => Matched(__pos, {
+                                                            Filter::Compare(op, l, r)
+                                                        }),
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => __parse_jsonpath_filter_test(__input, __state, __pos),
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_filter_test<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Filter> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "(");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_blank(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_jsonpath_filter(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, f) => {
+                                    let __seq_res = __parse_blank(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res = slice_eq(__input, __state, __pos, ")");
+                                            match __seq_res {
+                                                Matched(__pos, _) => Matched(__pos, { f }),
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = slice_eq(__input, __state, __pos, "@");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = vec![];
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __step_res = {
+                                    let __seq_res = __parse_blank(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let __seq_res =
+                                                __parse_jsonpath_segment(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, s) => Matched(__pos, { s }),
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __step_res {
+                                    Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            Matched(__repeat_pos, __repeat_value)
+                        };
+                        match __seq_res {
+                            Matched(__pos, s) => Matched(__pos, { Filter::Query(s) }),
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_filter_operand<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<FilterOperand> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = __parse_jsonpath_filter_path(__input, __state, __pos);
+            match __seq_res {
+                Matched(__pos, p) => Matched(__pos, { FilterOperand::Path(p) }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = __parse_jsonpath_string(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, s) => {
+                            Matched(__pos, { FilterOperand::Literal(Value::String(s)) })
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = __parse_json_literal(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, v) => Matched(__pos, { FilterOperand::Literal(v) }),
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_filter_path<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Vec<PathStep>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __seq_res = slice_eq(__input, __state, __pos, "@");
+        match __seq_res {
+            Matched(__pos, _) => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res =
+                                        __parse_jsonpath_path_step(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, p) => Matched(__pos, { p }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    Matched(__repeat_pos, __repeat_value)
+                };
+                match __seq_res {
+                    Matched(__pos, p) => Matched(__pos, { p }),
+                    Failed => Failed,
+                }
+            }
+            Failed => Failed,
+        }
+    }
+}
+
+fn __parse_jsonpath_path_step<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<PathStep> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, ".");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = __parse_jsonpath_member_name(__input, __state, __pos);
+                    match __seq_res {
+                        Matched(__pos, n) => Matched(__pos, { PathStep::Key(n) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "[");
+                    match __seq_res {
+                        Matched(__pos, _) => {
+                            let __seq_res = __parse_blank(__input, __state, __pos);
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = __parse_jsonpath_index(__input, __state, __pos);
+                                    match __seq_res {
+                                        Matched(__pos, i) => {
+                                            let __seq_res = __parse_blank(__input, __state, __pos);
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res =
+                                                        slice_eq(__input, __state, __pos, "]");
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            Matched(__pos, { PathStep::Index(i) })
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = slice_eq(__input, __state, __pos, "[");
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = __parse_blank(__input, __state, __pos);
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        let __seq_res =
+                                            __parse_jsonpath_string(__input, __state, __pos);
+                                        match __seq_res {
+                                            Matched(__pos, k) => {
+                                                let __seq_res =
+                                                    __parse_blank(__input, __state, __pos);
+                                                match __seq_res {
+                                                    Matched(__pos, _) => {
+                                                        let __seq_res =
+                                                            slice_eq(__input, __state, __pos, "]");
+                                                        match __seq_res {
+                                                            Matched(__pos, _) => {
+                                                                Matched(__pos, { PathStep::Key(k) })
+                                                            }
+                                                            Failed => Failed,
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_string<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<String> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "'");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = {
+                        let mut __repeat_pos = __pos;
+                        let mut __repeat_value = vec![];
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = __parse_jsonpath_s_char(__input, __state, __pos);
+                            match __step_res {
+                                Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
+                                    __repeat_value.push(__value);
+                                }
+                                Failed => {
+                                    break;
+                                }
+                            }
+                        }
+                        Matched(__repeat_pos, __repeat_value)
+                    };
+                    match __seq_res {
+                        Matched(__pos, s) => {
+                            let __seq_res = slice_eq(__input, __state, __pos, "'");
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { s.into_iter().collect() }),
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = slice_eq(__input, __state, __pos, "\"");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = vec![];
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __step_res = __parse_jsonpath_d_char(__input, __state, __pos);
+                                match __step_res {
+                                    Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            Matched(__repeat_pos, __repeat_value)
+                        };
+                        match __seq_res {
+                            Matched(__pos, s) => {
+                                let __seq_res = slice_eq(__input, __state, __pos, "\"");
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        Matched(__pos, { s.into_iter().collect() })
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_s_char<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_jsonpath_escape(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "\\'");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { '\'' }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = {
+                            __state.suppress_fail += 1;
+                            let __assert_res = if __input.len() > __pos {
+                                let (__ch, __next) = char_range_at(__input, __pos);
+                                match __ch {
+                                    '\'' | '\\' | '\0'...'\u{1f}' => Matched(__next, ()),
+                                    _ => __state.mark_failure(__pos, "['\\\0-\u{1f}]"),
+                                }
+                            } else {
+                                __state.mark_failure(__pos, "['\\\0-\u{1f}]")
+                            };
+                            __state.suppress_fail -= 1;
+                            match __assert_res {
+                                Failed => Matched(__pos, ()),
+                                Matched(..) => Failed,
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = {
+                                    let str_start = __pos;
+                                    match any_char(__input, __state, __pos) {
+                                        Matched(__newpos, _) => {
+                                            Matched(__newpos, &__input[str_start..__newpos])
+                                        }
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __seq_res {
+                                    Matched(__pos, c) => {
+                                        Matched(__pos, { c.chars().next().unwrap() })
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_d_char<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = __parse_jsonpath_escape(__input, __state, __pos);
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "\\\"");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { '"' }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = {
+                            __state.suppress_fail += 1;
+                            let __assert_res = if __input.len() > __pos {
+                                let (__ch, __next) = char_range_at(__input, __pos);
+                                match __ch {
+                                    '"' | '\\' | '\0'...'\u{1f}' => Matched(__next, ()),
+                                    _ => __state.mark_failure(__pos, "[\"\\\0-\u{1f}]"),
+                                }
+                            } else {
+                                __state.mark_failure(__pos, "[\"\\\0-\u{1f}]")
+                            };
+                            __state.suppress_fail -= 1;
+                            match __assert_res {
+                                Failed => Matched(__pos, ()),
+                                Matched(..) => Failed,
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = {
+                                    let str_start = __pos;
+                                    match any_char(__input, __state, __pos) {
+                                        Matched(__newpos, _) => {
+                                            Matched(__newpos, &__input[str_start..__newpos])
+                                        }
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __seq_res {
+                                    Matched(__pos, c) => {
+                                        Matched(__pos, { c.chars().next().unwrap() })
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_escape<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "\\b");
+            match __seq_res {
+                Matched(__pos, _) => Matched(__pos, { '\u{8}' }),
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __choice_res = {
+                    let __seq_res = slice_eq(__input, __state, __pos, "\\f");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { '\u{c}' }),
+                        Failed => Failed,
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __choice_res = {
+                            let __seq_res = slice_eq(__input, __state, __pos, "\\n");
+                            match __seq_res {
+                                Matched(__pos, _) => Matched(__pos, { '\n' }),
+                                Failed => Failed,
+                            }
+                        };
+                        match __choice_res {
+                            Matched(__pos, __value) => Matched(__pos, __value),
+                            Failed => {
+                                let __choice_res = {
+                                    let __seq_res = slice_eq(__input, __state, __pos, "\\r");
+                                    match __seq_res {
+                                        Matched(__pos, _) => Matched(__pos, { '\r' }),
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __choice_res {
+                                    Matched(__pos, __value) => Matched(__pos, __value),
+                                    Failed => {
+                                        let __choice_res = {
+                                            let __seq_res =
+                                                slice_eq(__input, __state, __pos, "\\t");
+                                            match __seq_res {
+                                                Matched(__pos, _) => Matched(__pos, { '\t' }),
+                                                Failed => Failed,
+                                            }
+                                        };
+                                        match __choice_res {
+                                            Matched(__pos, __value) => Matched(__pos, __value),
+                                            Failed => {
+                                                let __choice_res = {
+                                                    let __seq_res =
+                                                        slice_eq(__input, __state, __pos, "\\/");
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            Matched(__pos, { '/' })
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                };
+                                                match __choice_res {
+                                                    Matched(__pos, __value) => {
+                                                        Matched(__pos, __value)
+                                                    }
+                                                    Failed => {
+                                                        let __choice_res = {
+                                                            let __seq_res = slice_eq(
+                                                                __input, __state, __pos, "\\\\",
+                                                            );
+                                                            match __seq_res {
+                                                                Matched(__pos, _) => {
+                                                                    Matched(__pos, { '\\' })
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        };
+                                                        match __choice_res {
+                                                            Matched(__pos, __value) => {
+                                                                Matched(__pos, __value)
+                                                            }
+                                                            Failed => {
+                                                                __parse_jsonpath_unicode_escape(
+                                                                    __input, __state, __pos,
+                                                                )
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn __parse_jsonpath_unicode_escape<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<char> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, "\\u");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = {
+                        let str_start = __pos;
+                        match {
+                            let __seq_res = if __input.len() > __pos {
+                                let (__ch, __next) = char_range_at(__input, __pos);
+                                match __ch {
+                                    'd' | 'D' => Matched(__next, ()),
+                                    _ => __state.mark_failure(__pos, "[dD]"),
+                                }
+                            } else {
+                                __state.mark_failure(__pos, "[dD]")
+                            };
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = if __input.len() > __pos {
+                                        let (__ch, __next) = char_range_at(__input, __pos);
+                                        match __ch {
+                                            '8' | '9' | 'a' | 'b' | 'A' | 'B' => {
+                                                Matched(__next, ())
+                                            }
+                                            _ => __state.mark_failure(__pos, "[89abAB]"),
+                                        }
+                                    } else {
+                                        __state.mark_failure(__pos, "[89abAB]")
+                                    };
+                                    match __seq_res {
+                                        Matched(__pos, _) => {
+                                            let mut __repeat_pos = __pos;
+                                            let mut __repeat_value = vec![];
+                                            loop {
+                                                let __pos = __repeat_pos;
+                                                if __repeat_value.len() >= 2 {
+                                                    break;
+                                                }
+                                                let __step_res = if __input.len() > __pos {
+                                                    let (__ch, __next) =
+                                                        char_range_at(__input, __pos);
+                                                    match __ch {
+                                                        '0'...'9' | 'a'...'f' | 'A'...'F' => {
+                                                            Matched(__next, ())
+                                                        }
+                                                        _ => __state
+                                                            .mark_failure(__pos, "[0-9a-fA-F]"),
+                                                    }
+                                                } else {
+                                                    __state.mark_failure(__pos, "[0-9a-fA-F]")
+                                                };
+                                                match __step_res {
+                                                    Matched(__newpos, __value) => {
+                                                        __repeat_pos = __newpos;
+                                                        __repeat_value.push(__value);
+                                                    }
+                                                    Failed => {
+                                                        break;
+                                                    }
+                                                }
+                                            }
+                                            if __repeat_value.len() >= 2 {
+                                                Matched(__repeat_pos, ())
+                                            } else {
+                                                Failed
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        } {
+                            Matched(__newpos, _) => {
+                                Matched(__newpos, &__input[str_start..__newpos])
+                            }
+                            Failed => Failed,
+                        }
+                    };
+                    match __seq_res {
+                        Matched(__pos, h) => {
+                            let __seq_res = slice_eq(__input, __state, __pos, "\\u");
+                            match __seq_res {
+                                Matched(__pos, _) => {
+                                    let __seq_res = {
+                                        let str_start = __pos;
+                                        match {
+                                            let __seq_res = if __input.len() > __pos {
+                                                let (__ch, __next) = char_range_at(__input, __pos);
+                                                match __ch {
+                                                    'd' | 'D' => Matched(__next, ()),
+                                                    _ => __state.mark_failure(__pos, "[dD]"),
+                                                }
+                                            } else {
+                                                __state.mark_failure(__pos, "[dD]")
+                                            };
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    let __seq_res = if __input.len() > __pos {
+                                                        let (__ch, __next) =
+                                                            char_range_at(__input, __pos);
+                                                        match __ch {
+                                                            'c'...'f' | 'C'...'F' => {
+                                                                Matched(__next, ())
+                                                            }
+                                                            _ => __state
+                                                                .mark_failure(__pos, "[c-fC-F]"),
+                                                        }
+                                                    } else {
+                                                        __state.mark_failure(__pos, "[c-fC-F]")
+                                                    };
+                                                    match __seq_res {
+                                                        Matched(__pos, _) => {
+                                                            let mut __repeat_pos = __pos;
+                                                            let mut __repeat_value = vec![];
+                                                            loop {
+                                                                let __pos = __repeat_pos;
+                                                                if __repeat_value.len() >= 2 {
+                                                                    break;
+                                                                }
+                                                                let __step_res = if __input.len()
+                                                                    > __pos
+                                                                {
+                                                                    let (__ch, __next) =
+                                                                        char_range_at(
+                                                                            __input, __pos,
+                                                                        );
+                                                                    match __ch {
+                                                                        '0'...'9'
+                                                                        | 'a'...'f'
+                                                                        | 'A'...'F' => {
+                                                                            Matched(__next, ())
+                                                                        }
+                                                                        _ => __state.mark_failure(
+                                                                            __pos,
+                                                                            "[0-9a-fA-F]",
+                                                                        ),
+                                                                    }
+                                                                } else {
+                                                                    __state.mark_failure(
+                                                                        __pos,
+                                                                        "[0-9a-fA-F]",
+                                                                    )
+                                                                };
+                                                                match __step_res {
+                                                                    Matched(__newpos, __value) => {
+                                                                        __repeat_pos = __newpos;
+                                                                        __repeat_value
+                                                                            .push(__value);
+                                                                    }
+                                                                    Failed => {
+                                                                        break;
+                                                                    }
+                                                                }
+                                                            }
+                                                            if __repeat_value.len() >= 2 {
+                                                                Matched(__repeat_pos, ())
+                                                            } else {
+                                                                Failed
+                                                            }
+                                                        }
+                                                        Failed => Failed,
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        } {
+                                            Matched(__newpos, _) => {
+                                                Matched(__newpos, &__input[str_start..__newpos])
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    };
+                                    match __seq_res {
+                                        Matched(__pos, l) => Matched(__pos, {
+                                            let (h, l) = (
+                                                u32::from_str_radix(h, 16).unwrap(),
+                                                u32::from_str_radix(l, 16).unwrap(),
+                                            );
+                                            ::std::char::from_u32(
+                                                0x10000 + ((h - 0xD800) << 10) + (l - 0xDC00),
+                                            )
+                                            .unwrap()
+                                        }),
+                                        Failed => Failed,
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = slice_eq(__input, __state, __pos, "\\u");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = {
+                            __state.suppress_fail += 1;
+                            let __assert_res = {
+                                let __seq_res = if __input.len() > __pos {
+                                    let (__ch, __next) = char_range_at(__input, __pos);
+                                    match __ch {
+                                        'd' | 'D' => Matched(__next, ()),
+                                        _ => __state.mark_failure(__pos, "[dD]"),
+                                    }
+                                } else {
+                                    __state.mark_failure(__pos, "[dD]")
+                                };
+                                match __seq_res {
+                                    Matched(__pos, _) => {
+                                        if __input.len() > __pos {
+                                            let (__ch, __next) = char_range_at(__input, __pos);
+                                            match __ch {
+                                                '8' | '9' | 'a'...'f' | 'A'...'F' => {
+                                                    Matched(__next, ())
+                                                }
+                                                _ => __state.mark_failure(__pos, "[89a-fA-F]"),
+                                            }
+                                        } else {
+                                            __state.mark_failure(__pos, "[89a-fA-F]")
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            };
+                            __state.suppress_fail -= 1;
+                            match __assert_res {
+                                Failed => Matched(__pos, ()),
+                                Matched(..) => Failed,
+                            }
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let __seq_res = {
+                                    let str_start = __pos;
+                                    match {
+                                        let mut __repeat_pos = __pos;
+                                        let mut __repeat_value = vec![];
+                                        loop {
+                                            let __pos = __repeat_pos;
+                                            if __repeat_value.len() >= 4 {
+                                                break;
+                                            }
+                                            let __step_res = if __input.len() > __pos {
+                                                let (__ch, __next) = char_range_at(__input, __pos);
+                                                match __ch {
+                                                    '0'...'9' | 'a'...'f' | 'A'...'F' => {
+                                                        Matched(__next, ())
+                                                    }
+                                                    _ => __state.mark_failure(__pos, "[0-9a-fA-F]"),
+                                                }
+                                            } else {
+                                                __state.mark_failure(__pos, "[0-9a-fA-F]")
+                                            };
+                                            match __step_res {
+                                                Matched(__newpos, __value) => {
+                                                    __repeat_pos = __newpos;
+                                                    __repeat_value.push(__value);
+                                                }
+                                                Failed => {
+                                                    break;
+                                                }
+                                            }
+                                        }
+                                        if __repeat_value.len() >= 4 {
+                                            Matched(__repeat_pos, ())
+                                        } else {
+                                            Failed
+                                        }
+                                    } {
+                                        Matched(__newpos, _) => {
+                                            Matched(__newpos, &__input[str_start..__newpos])
+                                        }
+                                        Failed => Failed,
+                                    }
+                                };
+                                match __seq_res {
+                                    Matched(__pos, h) => Matched(__pos, {
+                                        ::std::char::from_u32(u32::from_str_radix(h, 16).unwrap())
+                                            .unwrap()
+                                    }),
+                                    Failed => Failed,
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+pub fn script<'input>(__input: &'input str) -> ParseResult<Script> {
+    #![allow(non_snake_case, unused)]
+    let mut __state = ParseState::new();
+    match __parse_script(__input, &mut __state, 0) {
+        Matched(__pos, __value) => {
+            if __pos == __input.len() {
+                return Ok(__value);
+            }
+        }
+        _ => {}
+    }
+    let (__line, __col) = pos_to_line(__input, __state.max_err_pos);
+    Err(ParseError {
+        line: __line,
+        column: __col,
+        offset: __state.max_err_pos,
+        expected: __state.expected,
+    })
+}
+
+pub fn jsonpath_script<'input>(__input: &'input str) -> ParseResult<Script> {
+    #![allow(non_snake_case, unused)]
+    let mut __state = ParseState::new();
+    match __parse_jsonpath_script(__input, &mut __state, 0) {
+        Matched(__pos, __value) => {
+            if __pos == __input.len() {
+                return Ok(__value);
+            }
+        }
+        _ => {}
     }
-    let (line, col) = pos_to_line(input, state.max_err_pos);
-    Err(ParseError{line: line,
-                   column: col,
-                   offset: state.max_err_pos,
-                   expected: state.expected,})
+    let (__line, __col) = pos_to_line(__input, __state.max_err_pos);
+    Err(ParseError {
+        line: __line,
+        column: __col,
+        offset: __state.max_err_pos,
+        expected: __state.expected,
+    })
 }
@@ -1,12 +1,20 @@
 extern crate serde_json as json;
 
-use std;
 use std::io;
 use std::collections::BTreeMap;
 use std::fmt;
+use std::cmp;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::borrow::Cow;
 
 use json::Value;
 
+use indexmap::IndexMap;
+
+use output::{Output, OutputFormat};
+use format::sprintf;
+
 #[derive(Debug)]
 pub enum ObjectIndexer {
     Wildcard,
@@ -29,30 +37,91 @@ impl ArraySlice {
         }
     }
 
-    pub fn to_range(&self, len: usize) -> std::ops::Range<usize> {
-        // TODO: handle negative
-        let start = match self.start {
-            Some(s) => s as usize,
-            None => 0,
-        };
+    // indices selected from an array of length `len`, in selection order.
+    // negative bounds count from the end, out-of-range bounds are clamped,
+    // and a negative step walks backwards (python/jsonpath semantics).
+    pub fn to_indices(&self, len: usize) -> Vec<usize> {
+        let len = len as i64;
+        let step = self.step.unwrap_or(1);
+        let mut indices = Vec::new();
 
-        let end = match self.end {
-            Some(e) => e as usize,
-            None => len,
-        };
+        if step == 0 {
+            return indices;
+        }
 
-        // TODO: impl step
-        return std::ops::Range{start:start, end:end}
+        let normalize = |i: i64| if i < 0 { i + len } else { i };
+
+        if step > 0 {
+            let start = self.start.map_or(0, |s| clamp(normalize(s), 0, len));
+            let end = self.end.map_or(len, |e| clamp(normalize(e), 0, len));
+
+            let mut i = start;
+            while i < end {
+                indices.push(i as usize);
+                i = match i.checked_add(step) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+        } else {
+            let start = self.start.map_or(len - 1, |s| clamp(normalize(s), -1, len - 1));
+            let end = self.end.map_or(-1, |e| clamp(normalize(e), -1, len - 1));
+
+            let mut i = start;
+            while i > end {
+                indices.push(i as usize);
+                i = match i.checked_add(step) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+        }
+
+        return indices;
+    }
+}
+
+fn clamp(v: i64, low: i64, high: i64) -> i64 {
+    if v < low {
+        low
+    } else if v > high {
+        high
+    } else {
+        v
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
 #[derive(Debug)]
 pub enum ActionExpr {
     Integer(i64),
+    Float(f64),
     String(String),
+    Bool(bool),
+    Null,
     Variable(String),
     ObjectIndex(String),
     ArrayIndex(i64),
+    Neg(Box<ActionExpr>),
+    Arith(ArithOp, Box<ActionExpr>, Box<ActionExpr>),
+    Compare(CmpOp, Box<ActionExpr>, Box<ActionExpr>),
+    And(Box<ActionExpr>, Box<ActionExpr>),
+    Or(Box<ActionExpr>, Box<ActionExpr>),
+    Not(Box<ActionExpr>),
+}
+
+// a chain of operators of the same precedence, grouped from the left
+pub fn fold_arith(first: ActionExpr, rest: Vec<(ArithOp, ActionExpr)>) -> ActionExpr
+{
+    rest.into_iter().fold(first, |left, (op, right)| ActionExpr::Arith(op, Box::new(left), Box::new(right)))
 }
 
 #[derive(Debug)]
@@ -61,17 +130,346 @@ pub struct Function {
     pub args: Vec<ActionExpr>,
 }
 
+#[derive(Debug)]
+pub enum Statement {
+    Call(Function),
+    // NAME = expr, which sets a runtime variable
+    Assign(String, ActionExpr),
+    // if cond { ... } else { ... }, where a missing else is empty
+    If(ActionExpr, Vec<Statement>, Vec<Statement>),
+}
+
 #[derive(Debug)]
 pub enum Selector {
     ForSelf(Sop),
     ForEach(Sop),
+    Descendant(Sop),
+    // segments of a standard jsonpath query, applied to every node of the
+    // current nodelist
+    PathChild(Sop),
+    PathDescendant(Sop),
 }
 
 #[derive(Debug)]
 pub enum Sop {
     ArraySlice(ArraySlice),
     ArrayIndex(i64),
+    ArrayUnion(Vec<i64>),
     Object(ObjectIndexer),
+    ObjectUnion(Vec<String>),
+    Filter(Filter),
+    Optional(Box<Sop>),
+    // json pointer reference token: an object key, or an array index
+    PointerToken(String),
+    // jsonpath selectors of any kind in one bracket, e.g. [0,'a',1:3]
+    Union(Vec<Sop>),
+}
+
+// the selector of a jsonpath bracket, a union if it holds more than one
+pub fn union_sop(mut sops: Vec<Sop>) -> Sop
+{
+    if sops.len() == 1 {
+        return sops.pop().unwrap();
+    }
+
+    return Sop::Union(sops);
+}
+
+// one step of a relative path inside a filter, e.g. '.price' or '.[0]',
+// or of the path of a selected value
+#[derive(Debug, Clone)]
+pub enum PathStep {
+    Key(String),
+    Index(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug)]
+pub enum FilterOperand {
+    Path(Vec<PathStep>),
+    Literal(Value),
+}
+
+// predicate of a '[?(...)]' selector, evaluated against each element
+#[derive(Debug)]
+pub enum Filter {
+    Exists(Vec<PathStep>),
+    Compare(CmpOp, FilterOperand, FilterOperand),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    // a jsonpath query from the element, which holds if it selects anything
+    Query(Vec<Selector>),
+}
+
+impl fmt::Display for Sop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &Sop::ArrayIndex(index) => write!(f, "[{}]", index),
+            &Sop::ArraySlice(ref slice) => write!(f, "{}", slice),
+            &Sop::Object(ObjectIndexer::Wildcard) => write!(f, "*"),
+            &Sop::Object(ObjectIndexer::Exact(ref key)) => write!(f, "{}", key),
+            &Sop::ArrayUnion(ref indices) => {
+                let indices: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", indices.join(","))
+            },
+            &Sop::ObjectUnion(ref keys) => {
+                let keys: Vec<String> = keys.iter().map(|k| format!("'{}'", k)).collect();
+                write!(f, "[{}]", keys.join(","))
+            },
+            &Sop::Filter(ref filter) => write!(f, "[?({})]", filter),
+            &Sop::Optional(ref sop) => write!(f, "{}?", sop),
+            &Sop::PointerToken(ref token) => write!(f, "/{}", token.replace("~", "~0").replace("/", "~1")),
+            &Sop::Union(ref sops) => {
+                try!(write!(f, "["));
+                for (i, sop) in sops.iter().enumerate() {
+                    if i > 0 {
+                        try!(write!(f, ","));
+                    }
+                    try!(fmt_union_member(f, sop));
+                }
+                write!(f, "]")
+            },
+        }
+    }
+}
+
+// a selector of a union, without the brackets it has on its own
+fn fmt_union_member(f: &mut fmt::Formatter, sop: &Sop) -> fmt::Result {
+    match sop {
+        &Sop::ArrayIndex(index) => write!(f, "{}", index),
+        &Sop::ArraySlice(ref slice) => slice.fmt_bounds(f),
+        &Sop::Object(ObjectIndexer::Exact(ref key)) => write!(f, "'{}'", key),
+        &Sop::Filter(ref filter) => write!(f, "?({})", filter),
+        sop => write!(f, "{}", sop),
+    }
+}
+
+impl fmt::Display for PathStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &PathStep::Key(ref key) => write!(f, ".{}", key),
+            &PathStep::Index(index) => write!(f, ".[{}]", index),
+        }
+    }
+}
+
+impl fmt::Display for CmpOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self {
+            &CmpOp::Eq => "==",
+            &CmpOp::Ne => "!=",
+            &CmpOp::Lt => "<",
+            &CmpOp::Le => "<=",
+            &CmpOp::Gt => ">",
+            &CmpOp::Ge => ">=",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+fn fmt_path(f: &mut fmt::Formatter, path: &Vec<PathStep>) -> fmt::Result {
+    if path.is_empty() {
+        return write!(f, "@");
+    }
+    for step in path {
+        try!(write!(f, "{}", step));
+    }
+    Ok(())
+}
+
+impl fmt::Display for FilterOperand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &FilterOperand::Path(ref path) => fmt_path(f, path),
+            &FilterOperand::Literal(ref value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &Filter::Exists(ref path) => fmt_path(f, path),
+            &Filter::Compare(op, ref left, ref right) => write!(f, "{} {} {}", left, op, right),
+            &Filter::And(ref left, ref right) => write!(f, "({} && {})", left, right),
+            &Filter::Or(ref left, ref right) => write!(f, "({} || {})", left, right),
+            &Filter::Not(ref filter) => write!(f, "!{}", filter),
+            &Filter::Query(ref selectors) => {
+                try!(write!(f, "@"));
+                for selector in selectors {
+                    match selector {
+                        &Selector::PathChild(ref sop) => try!(fmt_bracket(f, sop)),
+                        &Selector::PathDescendant(ref sop) => {
+                            try!(write!(f, ".."));
+                            try!(fmt_bracket(f, sop));
+                        },
+                        _ => {},
+                    }
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+// a jsonpath selector in brackets, which a union has of its own
+fn fmt_bracket(f: &mut fmt::Formatter, sop: &Sop) -> fmt::Result {
+    match sop {
+        &Sop::Union(_) => write!(f, "{}", sop),
+        sop => {
+            try!(write!(f, "["));
+            try!(fmt_union_member(f, sop));
+            write!(f, "]")
+        },
+    }
+}
+
+impl fmt::Display for ArithOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "*",
+            ArithOp::Div => "/",
+            ArithOp::Mod => "%",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// an operand of an operator, in parentheses if it has operators of its own
+fn fmt_operand(f: &mut fmt::Formatter, e: &ActionExpr) -> fmt::Result {
+    match e {
+        &ActionExpr::Arith(..) | &ActionExpr::Compare(..) | &ActionExpr::And(..) | &ActionExpr::Or(..) => write!(f, "({})", e),
+        e => write!(f, "{}", e),
+    }
+}
+
+impl fmt::Display for ActionExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &ActionExpr::Integer(i) => write!(f, "{}", i),
+            &ActionExpr::Float(x) => write!(f, "{:?}", x),
+            &ActionExpr::String(ref s) => write!(f, "{}", Value::String(s.clone())),
+            &ActionExpr::Bool(b) => write!(f, "{}", b),
+            &ActionExpr::Null => write!(f, "null"),
+            &ActionExpr::Variable(ref name) => write!(f, "{}", name),
+            &ActionExpr::ObjectIndex(ref key) => write!(f, ".{}", key),
+            &ActionExpr::ArrayIndex(index) => write!(f, ".[{}]", index),
+            &ActionExpr::Neg(ref e) => {
+                try!(write!(f, "-"));
+                fmt_operand(f, e)
+            },
+            &ActionExpr::Arith(op, ref left, ref right) => {
+                try!(fmt_operand(f, left));
+                try!(write!(f, " {} ", op));
+                fmt_operand(f, right)
+            },
+            &ActionExpr::Compare(op, ref left, ref right) => {
+                try!(fmt_operand(f, left));
+                try!(write!(f, " {} ", op));
+                fmt_operand(f, right)
+            },
+            &ActionExpr::And(ref left, ref right) => {
+                try!(fmt_operand(f, left));
+                try!(write!(f, " && "));
+                fmt_operand(f, right)
+            },
+            &ActionExpr::Or(ref left, ref right) => {
+                try!(fmt_operand(f, left));
+                try!(write!(f, " || "));
+                fmt_operand(f, right)
+            },
+            &ActionExpr::Not(ref e) => {
+                try!(write!(f, "!"));
+                fmt_operand(f, e)
+            },
+        }
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{}", self.name));
+        for arg in &self.args {
+            try!(write!(f, " {}", arg));
+        }
+        Ok(())
+    }
+}
+
+// the statements of a block and its closing brace
+fn fmt_block(f: &mut fmt::Formatter, block: &Vec<Statement>) -> fmt::Result {
+    for (i, statement) in block.iter().enumerate() {
+        try!(write!(f, "{} {}", if i > 0 { ";" } else { "" }, statement));
+    }
+    write!(f, " }}")
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &Statement::Call(ref func) => write!(f, "{}", func),
+            &Statement::Assign(ref name, ref e) => write!(f, "{} = {}", name, e),
+            &Statement::If(ref cond, ref then, ref otherwise) => {
+                try!(write!(f, "if {} {{", cond));
+                try!(fmt_block(f, then));
+                if !otherwise.is_empty() {
+                    try!(write!(f, " else {{"));
+                    try!(fmt_block(f, otherwise));
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl ArraySlice {
+    fn fmt_bounds(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bound = |b: Option<i64>| b.map_or(String::new(), |v| v.to_string());
+
+        try!(write!(f, "{}:{}", bound(self.start), bound(self.end)));
+        if let Some(step) = self.step {
+            try!(write!(f, ":{}", step));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ArraySlice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "["));
+        try!(self.fmt_bounds(f));
+        write!(f, "]")
+    }
+}
+
+// what a selector does when the selected element or key does not exist
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingPolicy {
+    Error,
+    Null,
+    Skip,
+}
+
+impl MissingPolicy {
+    // policy inside an optional ('?') segment, which never fails
+    pub fn optional(self) -> MissingPolicy {
+        match self {
+            MissingPolicy::Error => MissingPolicy::Skip,
+            policy => policy,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -84,22 +482,322 @@ pub enum ActionMode {
 pub struct Script {
     pub selector: Vec<Selector>,
     pub mode: ActionMode,
-    pub action: Vec<Function>,
+    pub action: Vec<Statement>,
 }
 
 
+// a syntax error in the input
+#[derive(Debug)]
+pub struct SyntaxError {
+    pub msg: String,
+    // position of the error, 0 when unknown
+    pub line: usize,
+    pub column: usize,
+    // text of the line the error is on, shown under the message
+    pub text: Option<String>,
+}
+
 #[derive(Debug)]
 pub enum JkError {
     Io(io::Error),
-    Parse(json::Error),
+    Parse(SyntaxError),
     Query(String),
     Action(String),
+    // an error in an input file, with the number of the record it happened
+    // in when the input is made of records
+    Input(Box<JkError>, String, Option<u64>),
+}
+
+pub fn syntax_error(msg: String, line: usize, column: usize) -> JkError
+{
+    JkError::Parse(SyntaxError { msg: msg, line: line, column: column, text: None })
+}
+
+// an error of the json parser, with its message split from its position
+pub fn json_error(e: json::Error) -> JkError
+{
+    if e.is_io() {
+        return JkError::Io(e.into());
+    }
+
+    let (line, column) = (e.line(), e.column());
+    let mut msg = e.to_string();
+    let position = format!(" at line {} column {}", line, column);
+
+    if msg.ends_with(&position) {
+        let len = msg.len() - position.len();
+        msg.truncate(len);
+    }
+
+    return syntax_error(msg, line, column);
+}
+
+// fills in the text of the line a syntax error is on, from the whole input
+pub fn with_error_text(mut e: JkError, input: &str) -> JkError
+{
+    match e {
+        JkError::Parse(ref mut e) if e.line > 0 && e.text.is_none() => {
+            e.text = input.lines().nth(e.line - 1).map(String::from);
+        },
+        _ => {},
+    }
+
+    return e;
+}
+
+// `text` with a caret under `column`, on lines of their own. long lines,
+// such as those of minified json, are cut to the part around the column.
+pub fn show_column(text: &str, column: usize) -> String
+{
+    const CONTEXT: usize = 40;
+
+    let chars: Vec<char> = text.chars().map(|c| if c == '\t' { ' ' } else { c }).collect();
+    let at = cmp::min(column.saturating_sub(1), chars.len());
+    let start = at.saturating_sub(CONTEXT);
+    let end = cmp::min(chars.len(), at + CONTEXT);
+
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < chars.len() { "..." } else { "" };
+    let shown: String = chars[start..end].iter().cloned().collect();
+
+    return format!("\n    {}{}{}\n    {}^", prefix, shown, suffix, " ".repeat(prefix.len() + at - start));
+}
+
+impl fmt::Display for JkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &JkError::Io(ref e) => write!(f, "{}", e),
+            &JkError::Parse(ref e) if e.line == 0 => write!(f, "syntax error: {}", e.msg),
+            &JkError::Parse(ref e) => write!(f, "syntax error at line {} column {}: {}", e.line, e.column, e.msg),
+            &JkError::Query(ref msg) => write!(f, "query error: {}", msg),
+            &JkError::Action(ref msg) => write!(f, "action error: {}", msg),
+            &JkError::Input(ref e, ref file, record) => {
+                match (&**e, record) {
+                    (&JkError::Parse(ref e), _) if e.line > 0 => {
+                        try!(write!(f, "{}:{}:{}: syntax error: {}", file, e.line, e.column, e.msg));
+                        match e.text {
+                            Some(ref text) => write!(f, "{}", show_column(text, e.column)),
+                            None => Ok(()),
+                        }
+                    },
+                    (&JkError::Query(_), Some(nr)) | (&JkError::Action(_), Some(nr)) => {
+                        write!(f, "{}: record {}: {}", file, nr, e)
+                    },
+                    _ => write!(f, "{}: {}", file, e),
+                }
+            },
+        }
+    }
 }
 
 
+// path of a value from the document root. steps are shared with the
+// parent path, so extending a path is cheap.
+#[derive(Debug, Clone)]
+pub struct Path(Option<Rc<PathLink>>);
+
+#[derive(Debug)]
+struct PathLink {
+    parent: Path,
+    step: PathStep,
+}
+
+impl Path {
+    pub fn root() -> Path {
+        Path(None)
+    }
+
+    pub fn child(&self, step: PathStep) -> Path {
+        Path(Some(Rc::new(PathLink { parent: self.clone(), step: step })))
+    }
+
+    pub fn key(&self, key: &str) -> Path {
+        self.child(PathStep::Key(String::from(key)))
+    }
+
+    pub fn index(&self, index: usize) -> Path {
+        self.child(PathStep::Index(index as i64))
+    }
+
+    // steps from the root down
+    pub fn steps(&self) -> Vec<&PathStep> {
+        let mut steps = Vec::new();
+        let mut curr = self;
+
+        while let Some(ref link) = curr.0 {
+            steps.push(&link.step);
+            curr = &link.parent;
+        }
+
+        steps.reverse();
+        return steps;
+    }
+
+    // normalized jsonpath, e.g. $['store']['book'][0]
+    pub fn to_jsonpath(&self) -> String {
+        let mut s = String::from("$");
+
+        for step in self.steps() {
+            match step {
+                &PathStep::Key(ref key) => {
+                    s.push_str("['");
+                    push_escaped(&mut s, key);
+                    s.push_str("']");
+                },
+                &PathStep::Index(index) => s.push_str(&format!("[{}]", index)),
+            }
+        }
+
+        return s;
+    }
+
+    // rfc 6901 json pointer, e.g. /store/book/0
+    pub fn to_pointer(&self) -> String {
+        let mut s = String::new();
+
+        for step in self.steps() {
+            match step {
+                &PathStep::Key(ref key) => {
+                    s.push('/');
+                    s.push_str(&key.replace("~", "~0").replace("/", "~1"));
+                },
+                &PathStep::Index(index) => s.push_str(&format!("/{}", index)),
+            }
+        }
+
+        return s;
+    }
+}
+
+// escapes a name for a normalized path, as in rfc 9535 section 2.7
+fn push_escaped(s: &mut String, name: &str) {
+    for c in name.chars() {
+        match c {
+            '\\' => s.push_str("\\\\"),
+            '\'' => s.push_str("\\'"),
+            '\u{8}' => s.push_str("\\b"),
+            '\u{c}' => s.push_str("\\f"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c < '\u{20}' => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+}
+
+// a selected value. values taken from the document are borrowed and
+// remember their path, while arrays and objects built by selectors (foreach,
+// slices, unions, filters...) hold nodes that each remember their own.
+#[derive(Debug, Clone)]
+pub enum Node<'a> {
+    Value(&'a Value, Path),
+    Array(Vec<Node<'a>>),
+    Object(IndexMap<String, Node<'a>>),
+    // a missing element kept as null by the missing policy
+    Missing,
+}
+
+// the elements of a node, each with its path
+pub enum Children<'a> {
+    Array(Vec<Node<'a>>),
+    Object(IndexMap<String, Node<'a>>),
+    Scalar(Node<'a>),
+}
+
+impl<'a> Node<'a> {
+    pub fn root(value: &'a Value) -> Node<'a> {
+        Node::Value(value, Path::root())
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            &Node::Value(_, ref path) => Some(path),
+            _ => None,
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        match self {
+            &Node::Value(&Value::Object(_), _) | &Node::Object(_) => true,
+            _ => false,
+        }
+    }
+
+    // the value of the node, copied only if it was built by a selector
+    pub fn into_value(self) -> Cow<'a, Value> {
+        match self {
+            Node::Value(value, _) => Cow::Borrowed(value),
+            node => Cow::Owned(node.to_value()),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            &Node::Value(value, _) => value.clone(),
+            &Node::Array(ref nodes) => Value::Array(nodes.iter().map(Node::to_value).collect()),
+            &Node::Object(ref nodes) => Value::Object(nodes.iter().map(|(k, n)| (k.clone(), n.to_value())).collect()),
+            &Node::Missing => Value::Null,
+        }
+    }
+
+    pub fn into_children(self) -> Children<'a> {
+        match self {
+            Node::Value(&Value::Array(ref vector), path) => {
+                let nodes = vector.iter().enumerate().map(|(i, v)| Node::Value(v, path.index(i)));
+                Children::Array(nodes.collect())
+            },
+            Node::Value(&Value::Object(ref object), path) => {
+                let nodes = object.iter().map(|(k, v)| (k.clone(), Node::Value(v, path.key(k))));
+                Children::Object(nodes.collect())
+            },
+            Node::Array(nodes) => Children::Array(nodes),
+            Node::Object(nodes) => Children::Object(nodes),
+            node => Children::Scalar(node),
+        }
+    }
+}
+
+// what sort of value `v` is, for messages
+pub fn kind_of(v: &Value) -> &'static str
+{
+    match v {
+        &Value::Null => "null",
+        &Value::Bool(_) => "a boolean",
+        &Value::Number(_) => "a number",
+        &Value::String(_) => "a string",
+        &Value::Array(_) => "an array",
+        &Value::Object(_) => "an object",
+    }
+}
+
+// the builtin variables that describe the element an action runs on, and
+// cannot be assigned
+pub const ELEMENT_VARIABLES: [&'static str; 4] = ["_k", "_v", "_p", "_ptr"];
+
+// the element an action runs on, seen through the _k, _v, _p and _ptr
+// builtin variables
+pub struct Current<'a> {
+    pub key: Option<Value>,
+    pub value: Cow<'a, Value>,
+    pub path: Option<Path>,
+}
+
+impl<'a> Current<'a> {
+    pub fn new(key: Option<Value>, node: Node<'a>) -> Current<'a> {
+        let path = node.path().cloned();
+
+        Current {
+            key: key,
+            value: node.into_value(),
+            path: path,
+        }
+    }
+}
+
 // function prototypes
 pub struct FunctionPrototype {
-    pub func: fn (&Vec<Value>) -> Result<Value, JkError>,
+    pub func: fn (&Runtime, &Function, &[&Value]) -> Result<Value, JkError>,
 }
 
 impl fmt::Debug for FunctionPrototype {
@@ -111,58 +809,162 @@ impl fmt::Debug for FunctionPrototype {
 // runtime
 pub struct Runtime {
     variables: BTreeMap<String, Value>,
+    // shared with the functions, which only get to read the runtime
+    pub output: RefCell<Output>,
 }
 
 impl Runtime {
-    pub fn new() -> Runtime
+    // a runtime with the variables that have defaults set: OFS, the output
+    // field separator, and ORS, the output record separator
+    pub fn new(output: Output) -> Runtime
     {
+        let mut variables = BTreeMap::new();
+        variables.insert(String::from("OFS"), Value::String(String::from(" ")));
+        variables.insert(String::from("ORS"), Value::String(String::from("\n")));
+
         Runtime {
-            variables: BTreeMap::new(),
+            variables: variables,
+            output: RefCell::new(output),
         }
     }
     
-    pub fn var_get(&self, name: &String) -> Value
+    pub fn var_get(&self, name: &str) -> Option<&Value>
     {
-        match self.variables.get(name) {
-            Some(v) => v.clone(),
-            None => Value::Null,
-        }
+        self.variables.get(name)
     }
 
     pub fn var_set(&mut self, name: &String, value: Value)
     {
         self.variables.insert(name.clone(), value);
     }
+}
 
-    pub fn var_delete(&mut self, name: &String)
-    {
-        self.variables.remove(name);
+// the name of the column an argument is printed in: the key or variable
+// it reads, or the expression itself
+fn column_name(e: &ActionExpr) -> String
+{
+    match e {
+        &ActionExpr::ObjectIndex(ref key) => key.clone(),
+        &ActionExpr::Variable(ref name) => name.clone(),
+        e => e.to_string(),
     }
 }
 
-fn builtin_print(args: &Vec<Value>) -> Result<Value, JkError>
+// the text of a separator variable; a value other than a string stands for
+// its json
+fn separator<'a>(runtime: &'a Runtime, name: &str) -> Cow<'a, str>
 {
-    for a in args {
-        match a {
-            &Value::String(ref s) => print!("{} ", s),
-            _ => {
-                let pretty = try!(json::to_string_pretty(a).map_err(JkError::Parse));
-                print!("{} ", pretty);
-            },
-        }
+    match runtime.var_get(name) {
+        Some(&Value::String(ref s)) => Cow::Borrowed(s),
+        Some(v) => Cow::Owned(v.to_string()),
+        None => Cow::Borrowed(""),
     }
+}
+
+fn print_row(runtime: &Runtime, func: &Function, args: &[&Value], compact: bool) -> Result<Value, JkError>
+{
+    let names = || func.args.iter().map(column_name).collect();
+    let (ofs, ors) = (separator(runtime, "OFS"), separator(runtime, "ORS"));
+    try!(runtime.output.borrow_mut().row(names, args, compact, &ofs, &ors).map_err(|e| JkError::Action(e.to_string())));
 
-    print!("\n");
-    
     return Ok(Value::Null);
 }
 
+fn builtin_print(runtime: &Runtime, func: &Function, args: &[&Value]) -> Result<Value, JkError>
+{
+    print_row(runtime, func, args, false)
+}
+
+// prints like 'p', with arrays and objects on one line
+fn builtin_print_compact(runtime: &Runtime, func: &Function, args: &[&Value]) -> Result<Value, JkError>
+{
+    print_row(runtime, func, args, true)
+}
+
+
+// printf FORMAT ARG..., which prints the arguments as the format tells, and
+// nothing else: neither OFS nor ORS
+fn builtin_printf(runtime: &Runtime, _func: &Function, args: &[&Value]) -> Result<Value, JkError>
+{
+    let text = match args.split_first() {
+        Some((&&Value::String(ref format), args)) => try!(sprintf(format, args).map_err(JkError::Action)),
+        _ => return Err(JkError::Action(String::from("the first argument must be a format string"))),
+    };
+
+    let mut output = runtime.output.borrow_mut();
+
+    if output.format() != OutputFormat::Text {
+        return Err(JkError::Action(String::from("printf only writes text output")));
+    }
+
+    output.text(&text);
+    return Ok(Value::Null);
+}
 
 pub fn make_builtin_funcs() -> BTreeMap<String, FunctionPrototype>
 {
     let mut m = BTreeMap::new();
 
     m.insert(String::from("p"), FunctionPrototype { func: builtin_print });
+    m.insert(String::from("pc"), FunctionPrototype { func: builtin_print_compact });
+    m.insert(String::from("printf"), FunctionPrototype { func: builtin_printf });
 
     return m;
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(start: Option<i64>, end: Option<i64>, step: Option<i64>, len: usize) -> Vec<usize> {
+        ArraySlice::new(start, end, step).to_indices(len)
+    }
+
+    #[test]
+    fn slice_bounds() {
+        assert_eq!(indices(None, None, None, 4), vec![0, 1, 2, 3]);
+        assert_eq!(indices(Some(1), Some(3), None, 4), vec![1, 2]);
+        assert_eq!(indices(Some(1), None, Some(2), 5), vec![1, 3]);
+        assert_eq!(indices(None, None, Some(0), 4), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn slice_negative_bounds() {
+        assert_eq!(indices(Some(-2), None, None, 4), vec![2, 3]);
+        assert_eq!(indices(None, Some(-1), None, 4), vec![0, 1, 2]);
+        assert_eq!(indices(Some(-3), Some(-1), None, 4), vec![1, 2]);
+    }
+
+    #[test]
+    fn slice_clamping() {
+        assert_eq!(indices(Some(-10), Some(10), None, 3), vec![0, 1, 2]);
+        assert_eq!(indices(Some(5), None, None, 3), Vec::<usize>::new());
+        assert_eq!(indices(Some(10), Some(-10), Some(-1), 3), vec![2, 1, 0]);
+        assert_eq!(indices(None, None, None, 0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn slice_negative_step() {
+        assert_eq!(indices(None, None, Some(-1), 4), vec![3, 2, 1, 0]);
+        assert_eq!(indices(Some(3), Some(0), Some(-2), 5), vec![3, 1]);
+        assert_eq!(indices(Some(-1), Some(-3), Some(-1), 4), vec![3, 2]);
+    }
+
+    #[test]
+    fn slice_overflowing_step() {
+        assert_eq!(indices(Some(1), None, Some(i64::MAX), 4), vec![1]);
+        assert_eq!(indices(Some(1), Some(i64::MAX), Some(i64::MAX), 4), vec![1]);
+        assert_eq!(indices(Some(-2), None, Some(i64::MIN), 4), vec![2]);
+        assert_eq!(indices(None, None, Some(i64::MIN), 4), vec![3]);
+    }
+
+    #[test]
+    fn normalized_path_escapes() {
+        let path = Path::root().key("it's").index(0).key("a\\b");
+        assert_eq!(path.to_jsonpath(), "$['it\\'s'][0]['a\\\\b']");
+
+        let path = Path::root().key("\u{8}\u{c}\n\r\t").key("\u{0}\u{1f} \u{7f}");
+        assert_eq!(path.to_jsonpath(), "$['\\b\\f\\n\\r\\t']['\\u0000\\u001f \u{7f}']");
+    }
+}
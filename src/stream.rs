@@ -0,0 +1,510 @@
+use std::io;
+use std::io::Read;
+
+use json;
+use json::Value;
+
+use script::{JkError, json_error, syntax_error};
+
+
+#[derive(Debug, PartialEq)]
+pub enum Kind {
+    Object,
+    Array,
+    Scalar,
+}
+
+// reads a json document incrementally: objects and arrays are walked one
+// member at a time, and only the values asked for are parsed into `Value`s,
+// so a document never has to be held in memory as a whole.
+pub struct Scanner<R: Read> {
+    bytes: io::Bytes<R>,
+    peeked: Option<u8>,
+    line: usize,
+    column: usize,
+    // for each open object or array, the byte that closes it and whether no
+    // member was read yet
+    open: Vec<(u8, bool)>,
+}
+
+impl<R: Read> Scanner<R> {
+    pub fn new(reader: R) -> Scanner<R>
+    {
+        Scanner {
+            bytes: reader.bytes(),
+            peeked: None,
+            line: 1,
+            column: 0,
+            open: Vec::new(),
+        }
+    }
+
+    // an error at the next byte, which is left unread
+    fn error(&self, msg: &str) -> JkError
+    {
+        syntax_error(String::from(msg), self.line, self.column + 1)
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, JkError>
+    {
+        if self.peeked.is_none() {
+            match self.bytes.next() {
+                Some(Ok(b)) => self.peeked = Some(b),
+                Some(Err(e)) => return Err(JkError::Io(e)),
+                None => return Ok(None),
+            }
+        }
+
+        return Ok(self.peeked);
+    }
+
+    fn bump(&mut self) -> Result<Option<u8>, JkError>
+    {
+        let b = try!(self.peek());
+        self.peeked = None;
+
+        if b == Some(b'\n') {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+
+        return Ok(b);
+    }
+
+    // skips whitespace and peeks at the byte that follows it
+    fn skip_ws(&mut self) -> Result<Option<u8>, JkError>
+    {
+        loop {
+            match try!(self.peek()) {
+                Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') => { try!(self.bump()); },
+                b => return Ok(b),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8, msg: &str) -> Result<(), JkError>
+    {
+        if try!(self.skip_ws()) == Some(expected) {
+            try!(self.bump());
+            return Ok(());
+        }
+
+        return Err(self.error(msg));
+    }
+
+    // kind of the next value, which is left unread
+    pub fn kind(&mut self) -> Result<Kind, JkError>
+    {
+        match try!(self.skip_ws()) {
+            Some(b'{') => Ok(Kind::Object),
+            Some(b'[') => Ok(Kind::Array),
+            Some(_) => Ok(Kind::Scalar),
+            None => Err(self.error("EOF while parsing a value")),
+        }
+    }
+
+    pub fn begin_object(&mut self) -> Result<(), JkError>
+    {
+        try!(self.expect(b'{', "expected value"));
+        self.open.push((b'}', true));
+        return Ok(());
+    }
+
+    pub fn begin_array(&mut self) -> Result<(), JkError>
+    {
+        try!(self.expect(b'[', "expected value"));
+        self.open.push((b']', true));
+        return Ok(());
+    }
+
+    // moves past the ',' before the next member of the open object or array.
+    // returns false, having consumed `close`, when there are no more members.
+    fn next_member(&mut self, close: u8, msg: &str) -> Result<bool, JkError>
+    {
+        let first = self.open.last().map_or(true, |&(_, first)| first);
+
+        match try!(self.skip_ws()) {
+            Some(b) if b == close => {
+                try!(self.bump());
+                self.open.pop();
+                return Ok(false);
+            },
+            Some(b',') if !first => { try!(self.bump()); },
+            None => return Err(self.error(msg)),
+            Some(_) if !first => return Err(self.error(msg)),
+            Some(_) => {},
+        }
+
+        if let Some(&mut (_, ref mut first)) = self.open.last_mut() {
+            *first = false;
+        }
+
+        return Ok(true);
+    }
+
+    // key of the next member of the open object, with its ':' consumed, or
+    // None at the end of the object
+    pub fn next_key(&mut self) -> Result<Option<String>, JkError>
+    {
+        if !try!(self.next_member(b'}', "expected `,` or `}`")) {
+            return Ok(None);
+        }
+
+        if try!(self.skip_ws()) != Some(b'"') {
+            return Err(self.error("key must be a string"));
+        }
+
+        let key = match try!(self.read_value()) {
+            Value::String(key) => key,
+            _ => return Err(self.error("key must be a string")),
+        };
+
+        try!(self.expect(b':', "expected `:`"));
+        return Ok(Some(key));
+    }
+
+    // whether the open array has another element to read
+    pub fn next_element(&mut self) -> Result<bool, JkError>
+    {
+        self.next_member(b']', "expected `,` or `]`")
+    }
+
+    // skips what is left of the open objects and arrays, then checks that
+    // nothing follows the document
+    pub fn finish(&mut self) -> Result<(), JkError>
+    {
+        while let Some(&(close, _)) = self.open.last() {
+            if close == b'}' {
+                while try!(self.next_key()).is_some() {
+                    try!(self.skip_value());
+                }
+            } else {
+                while try!(self.next_element()) {
+                    try!(self.skip_value());
+                }
+            }
+        }
+
+        match try!(self.skip_ws()) {
+            None => Ok(()),
+            Some(_) => Err(self.error("trailing characters")),
+        }
+    }
+
+    pub fn read_value(&mut self) -> Result<Value, JkError>
+    {
+        try!(self.skip_ws());
+
+        let (line, column) = (self.line, self.column);
+        let mut buf = Vec::new();
+        try!(self.scan_value(Some(&mut buf)));
+
+        // report errors at their position in the whole document
+        return json::from_slice(&buf).map_err(|e| {
+            let mut e = json_error(e);
+            if let JkError::Parse(ref mut e) = e {
+                if e.line == 1 {
+                    e.column += column;
+                }
+                if e.line > 0 {
+                    e.line += line - 1;
+                }
+            }
+            e
+        });
+    }
+
+    pub fn skip_value(&mut self) -> Result<(), JkError>
+    {
+        try!(self.skip_ws());
+        return self.scan_value(None);
+    }
+
+    fn take(&mut self, buf: &mut Option<&mut Vec<u8>>) -> Result<Option<u8>, JkError>
+    {
+        let b = try!(self.bump());
+
+        if let (&mut Some(ref mut buf), Some(b)) = (buf, b) {
+            buf.push(b);
+        }
+
+        return Ok(b);
+    }
+
+    // reads the bytes of one value, copying them into `buf` if given. only
+    // the nesting is checked here, the rest is left to the json parser.
+    fn scan_value(&mut self, mut buf: Option<&mut Vec<u8>>) -> Result<(), JkError>
+    {
+        match try!(self.peek()) {
+            None => Err(self.error("EOF while parsing a value")),
+            Some(b'"') => self.scan_string(&mut buf),
+            Some(b'{') | Some(b'[') => self.scan_container(&mut buf),
+            Some(_) => self.scan_scalar(&mut buf),
+        }
+    }
+
+    fn scan_container(&mut self, buf: &mut Option<&mut Vec<u8>>) -> Result<(), JkError>
+    {
+        let mut depth = 0;
+
+        loop {
+            match try!(self.peek()) {
+                None => return Err(self.error("EOF while parsing a value")),
+                Some(b'"') => try!(self.scan_string(buf)),
+                Some(b'{') | Some(b'[') => {
+                    try!(self.take(buf));
+                    depth += 1;
+                },
+                Some(b'}') | Some(b']') => {
+                    try!(self.take(buf));
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                },
+                Some(_) => { try!(self.take(buf)); },
+            }
+        }
+    }
+
+    // numbers, true, false and null run up to the next delimiter
+    fn scan_scalar(&mut self, buf: &mut Option<&mut Vec<u8>>) -> Result<(), JkError>
+    {
+        let mut empty = true;
+
+        loop {
+            match try!(self.peek()) {
+                None | Some(b',') | Some(b'}') | Some(b']') | Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') => break,
+                Some(_) => { try!(self.take(buf)); },
+            }
+            empty = false;
+        }
+
+        if empty {
+            return Err(self.error("expected value"));
+        }
+
+        return Ok(());
+    }
+
+    fn scan_string(&mut self, buf: &mut Option<&mut Vec<u8>>) -> Result<(), JkError>
+    {
+        try!(self.take(buf));
+
+        loop {
+            match try!(self.take(buf)) {
+                None => return Err(self.error("EOF while parsing a string")),
+                Some(b'"') => return Ok(()),
+                Some(b'\\') => { try!(self.take(buf)); },
+                Some(_) => {},
+            }
+        }
+    }
+}
+
+// lines longer than this are not kept
+const MAX_KEPT: usize = 64 * 1024;
+
+// passes an input through, keeping the line being read and the one before,
+// so that a syntax error in an input that cannot be read again, such as
+// stdin, can still show the line it is on
+pub struct RecentLines<R: Read> {
+    reader: R,
+    // number of the line being read
+    line: usize,
+    // None once a line is too long to keep
+    current: Option<Vec<u8>>,
+    previous: Option<Vec<u8>>,
+}
+
+impl<R: Read> RecentLines<R> {
+    pub fn new(reader: R) -> RecentLines<R>
+    {
+        RecentLines {
+            reader: reader,
+            line: 1,
+            current: Some(Vec::new()),
+            previous: None,
+        }
+    }
+
+    // line `line` as far as it was read, if it is still kept
+    pub fn line(&self, line: usize) -> Option<String>
+    {
+        let kept = if line == self.line {
+            &self.current
+        } else if line + 1 == self.line {
+            &self.previous
+        } else {
+            return None;
+        };
+
+        kept.as_ref().map(|bytes| String::from_utf8_lossy(bytes).trim_end_matches('\r').to_string())
+    }
+
+    // gives a syntax error the line it is on
+    pub fn error_text(&self, mut e: JkError) -> JkError
+    {
+        if let JkError::Parse(ref mut e) = e {
+            if e.line > 0 && e.text.is_none() {
+                e.text = self.line(e.line);
+            }
+        }
+
+        return e;
+    }
+}
+
+impl<R: Read> Read for RecentLines<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+    {
+        let n = try!(self.reader.read(buf));
+
+        for &b in &buf[..n] {
+            if b == b'\n' {
+                self.previous = self.current.take();
+                self.current = Some(Vec::new());
+                self.line += 1;
+                continue;
+            }
+
+            match self.current {
+                Some(ref line) if line.len() == MAX_KEPT => self.current = None,
+                Some(ref mut line) => line.push(b),
+                None => {},
+            }
+        }
+
+        return Ok(n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scanner(text: &str) -> Scanner<&[u8]> {
+        Scanner::new(text.as_bytes())
+    }
+
+    fn value(text: &str) -> Value {
+        json::from_str(text).unwrap()
+    }
+
+    fn position(e: JkError) -> (String, usize, usize) {
+        match e {
+            JkError::Parse(e) => (e.msg, e.line, e.column),
+            e => panic!("unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    fn skip_nested() {
+        let mut s = scanner(r#"{"a": {"b": [1, {"c": "]}"}], "e": {}}, "d": [2]}"#);
+        s.begin_object().unwrap();
+        assert_eq!(s.next_key().unwrap(), Some(String::from("a")));
+        assert_eq!(s.kind().unwrap(), Kind::Object);
+        s.skip_value().unwrap();
+        assert_eq!(s.next_key().unwrap(), Some(String::from("d")));
+        assert_eq!(s.read_value().unwrap(), value("[2]"));
+        assert_eq!(s.next_key().unwrap(), None);
+    }
+
+    #[test]
+    fn escaped_quotes() {
+        let mut s = scanner(r#"["a \"b\" c\\", "]\"", 3]"#);
+        s.begin_array().unwrap();
+        assert!(s.next_element().unwrap());
+        assert_eq!(s.read_value().unwrap(), value(r#""a \"b\" c\\""#));
+        assert!(s.next_element().unwrap());
+        s.skip_value().unwrap();
+        assert!(s.next_element().unwrap());
+        assert_eq!(s.read_value().unwrap(), value("3"));
+        assert!(!s.next_element().unwrap());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut s = scanner(r#"{"a": [1, {"b": "c"#);
+        s.begin_object().unwrap();
+        s.next_key().unwrap();
+        let (msg, line, column) = position(s.skip_value().unwrap_err());
+        assert_eq!((msg.as_str(), line, column), ("EOF while parsing a string", 1, 20));
+
+        let mut s = scanner("[1, 2");
+        s.begin_array().unwrap();
+        assert!(s.next_element().unwrap());
+        s.skip_value().unwrap();
+        assert!(s.next_element().unwrap());
+        s.skip_value().unwrap();
+        let (msg, _, _) = position(s.next_element().unwrap_err());
+        assert_eq!(msg, "expected `,` or `]`");
+    }
+
+    #[test]
+    fn error_position() {
+        let mut s = scanner("{\"a\": 1\n  \"b\": 2}");
+        s.begin_object().unwrap();
+        s.next_key().unwrap();
+        s.skip_value().unwrap();
+        assert_eq!(position(s.next_key().unwrap_err()), (String::from("expected `,` or `}`"), 2, 3));
+
+        // errors of the json parser are placed in the whole document, where
+        // the parser would have put them
+        let text = "[\n  1,\n  {\"x\": tru}]";
+        let whole = json::from_str::<Value>(text).unwrap_err();
+        let mut s = scanner(text);
+        s.begin_array().unwrap();
+        s.next_element().unwrap();
+        s.skip_value().unwrap();
+        s.next_element().unwrap();
+        let (_, line, column) = position(s.read_value().unwrap_err());
+        assert_eq!((line, column), (whole.line(), whole.column()));
+    }
+
+    #[test]
+    fn finish() {
+        let mut s = scanner(r#"{"a": [1, {"b": "]"}], "c": [2, 3], "d": {}} "#);
+        s.begin_object().unwrap();
+        s.next_key().unwrap();
+        s.begin_array().unwrap();
+        s.next_element().unwrap();
+        s.skip_value().unwrap();
+        s.finish().unwrap();
+
+        let mut s = scanner("[1, 2] garbage");
+        s.begin_array().unwrap();
+        while s.next_element().unwrap() {
+            s.skip_value().unwrap();
+        }
+        assert_eq!(position(s.finish().unwrap_err()), (String::from("trailing characters"), 1, 8));
+
+        let mut s = scanner(r#"{"items":[1,2] , "x": }"#);
+        s.begin_object().unwrap();
+        s.next_key().unwrap();
+        s.skip_value().unwrap();
+        assert_eq!(position(s.finish().unwrap_err()), (String::from("expected value"), 1, 23));
+    }
+
+    #[test]
+    fn recent_lines() {
+        let mut input = RecentLines::new("[1,\r\n 2,\n 3 x]".as_bytes());
+        let e = {
+            let mut s = Scanner::new(&mut input);
+            s.begin_array().unwrap();
+            for _ in 0..3 {
+                s.next_element().unwrap();
+                s.skip_value().unwrap();
+            }
+            s.next_element().unwrap_err()
+        };
+        match input.error_text(e) {
+            JkError::Parse(e) => assert_eq!((e.line, e.column, e.text), (3, 4, Some(String::from(" 3 x")))),
+            e => panic!("unexpected error {}", e),
+        }
+        assert_eq!(input.line(2), Some(String::from(" 2,")));
+        assert_eq!(input.line(1), None);
+    }
+}
@@ -0,0 +1,224 @@
+use parse::ParseError;
+
+use script::show_column;
+
+
+// what the parser expected, in plain words. each entry is the tokens that
+// bring a phrase in, the tokens it also covers, and the phrase itself; an
+// empty phrase drops its tokens, which would only be noise. entries are
+// listed in the order the phrases are shown.
+const PHRASES: &'static [(&'static [&'static str], &'static [&'static str], &'static str)] = &[
+    // '+' is only expected along with the other operators, one of which is
+    // '%', not to be taken for the mode separator
+    (&["+"], &["-", "*", "/", "%"], "an arithmetic operator"),
+    // '@' alone is the current element of a filter
+    (&["%"], &["@"], "'@' or '%' mode separator"),
+    // the rest of a name being read, such as the function name in '.a @ p'
+    (&["[a-zA-Z0-9_]", "[a-zA-Z0-9_\u{80}-\u{10ffff}]"], &["-"], ""),
+    // an assignment, where a function name may be followed by arguments
+    (&["="], &[], ""),
+    (&["<character>"], &["\"", "'", "\\", "\\'", "\\\"", "\\/", "\\b", "\\f", "\\n", "\\r", "\\t", "['\\]"], "the closing quote"),
+    (&["true", "false", "null"], &["\"", "'", "-", "0", "[1-9]"], "a value"),
+    // the rest of a number being read: its digits, fraction or exponent
+    (&["[eE]"], &["[0-9]", "[+-]", "."], ""),
+    (&["[0-9]", "[+-]"], &[], ""),
+    (&["0", "[1-9]"], &["-"], "a number"),
+    // an index that failed to match for being too large
+    (&["<i64>"], &[], "an index that fits in 64 bits"),
+    (&["<i-json>"], &[], "an integer from -9007199254740991 to 9007199254740991"),
+    // a number literal that failed to match for being too large
+    (&["<f64>"], &[], "a number in the range of a double"),
+    // the digits of a jsonpath '\u' escape, where a surrogate comes in pairs
+    (&["[0-9a-fA-F]"], &["[dD]"], "a hex digit"),
+    (&["[dD]", "[c-fC-F]"], &[], "a low surrogate, '\\uDC00' to '\\uDFFF'"),
+    (&["[89abAB]"], &[], "a high surrogate, '\\uD800' to '\\uDBFF', before a low one"),
+    (&["\"", "'"], &[], "a quoted string"),
+    (&["[a-zA-Z_]", "[a-zA-Z_\u{80}-\u{10ffff}]"], &[], "a name"),
+    (&["[^/ \t]"], &[], "a key"),
+    (&["==", "!=", "<", "<=", ">", ">="], &[], "a comparison"),
+    (&["&&", "||"], &[], "a logical operator"),
+    (&[";"], &[], "';' and another function"),
+];
+
+// spaces in jk programs, and those of jsonpath queries, which may break lines
+const SPACES: [&'static str; 2] = ["[ \t]", "[ \t\n\r]"];
+
+// a description of a syntax error in the program, with the program itself
+// and a caret under the error, and hints at what may be wrong
+pub fn describe(program: &str, e: &ParseError, jsonpath: bool) -> String
+{
+    let mut msg = String::from("syntax error in program at ");
+
+    if e.line > 1 {
+        msg.push_str(&format!("line {}, ", e.line));
+    }
+
+    msg.push_str(&format!("column {}: ", e.column));
+
+    let rest = &program[e.offset..];
+    msg.push_str(&match rest.chars().next() {
+        Some(c) => format!("unexpected '{}', ", c),
+        None => String::from("unexpected end of program, "),
+    });
+
+    let expected = expected_phrases(e);
+    msg.push_str("expected ");
+    msg.push_str(&join_phrases(&expected));
+
+    let line = program.lines().nth(e.line - 1).unwrap_or("");
+    msg.push_str(&show_column(line, e.column));
+
+    for hint in hints(program, e, &expected, jsonpath) {
+        msg.push_str("\nhint: ");
+        msg.push_str(hint);
+    }
+
+    return msg;
+}
+
+fn expected_phrases(e: &ParseError) -> Vec<String>
+{
+    let mut tokens = e.expected.clone();
+    let mut phrases = Vec::new();
+
+    for &(brought, covered, phrase) in PHRASES {
+        if !brought.iter().any(|t| tokens.contains(t)) {
+            continue;
+        }
+
+        for t in brought.iter().chain(covered.iter()) {
+            tokens.remove(t);
+        }
+
+        if !phrase.is_empty() {
+            phrases.push(String::from(phrase));
+        }
+    }
+
+    // the remaining tokens are punctuation, shown as they are to be typed;
+    // a space is only worth mentioning when nothing else would do
+    let mut space = false;
+    for t in SPACES.iter() {
+        space |= tokens.remove(t);
+    }
+    let mut rest: Vec<&str> = tokens.into_iter().collect();
+    rest.sort();
+
+    for t in rest {
+        phrases.push(format!("'{}'", t));
+    }
+
+    if phrases.is_empty() && space {
+        phrases.push(String::from("a space"));
+    }
+
+    return phrases;
+}
+
+// "a", "a or b", "a, b or c"
+fn join_phrases(phrases: &[String]) -> String
+{
+    match phrases.split_last() {
+        None => String::from("nothing more"),
+        Some((last, [])) => last.clone(),
+        Some((last, init)) => format!("{} or {}", init.join(", "), last),
+    }
+}
+
+// suggestions for the mistakes commonly behind an error
+fn hints(program: &str, e: &ParseError, expected: &[String], jsonpath: bool) -> Vec<&'static str>
+{
+    let before = &program[..e.offset];
+    let rest = &program[e.offset..];
+    let mut hints = Vec::new();
+
+    if jsonpath {
+        if e.offset == 0 && e.expected.contains("$") {
+            hints.push("with -j the selector is a jsonpath query, which starts with '$'");
+        }
+        return hints;
+    }
+
+    if program.trim_start().starts_with('$') {
+        hints.push("jsonpath queries, which start with '$', need the -j option");
+    } else if rest.starts_with('[') && !before.ends_with('.') {
+        hints.push("index arrays with '.[0]' rather than '[0]', or use -j for jsonpath queries");
+    }
+
+    if e.expected.contains("%") && !program.contains('@') && !program.contains('%') {
+        hints.push("a program is '<selector> <mode> <action>', where mode '@' runs the action on the selection and '%' on each of its elements, e.g. '.store.book % p .title'");
+    }
+
+    if rest.starts_with('-') && e.expected.contains("+") {
+        hints.push("a '-' between two values needs spaces around it, as in '.a - 1'; '-1' is an argument of its own");
+    }
+
+    if rest.starts_with('=') && e.expected.contains("==") {
+        hints.push("values compare with '=='; a single '=' sets a variable, as in 'OFS = \",\"'");
+    }
+
+    let word = before.trim_end().rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-')).next();
+    if rest.starts_with('=') && ["true", "false", "null"].contains(&word.unwrap_or("")) {
+        hints.push("true, false and null are values, which cannot be set");
+    }
+
+    if rest.trim().is_empty() && e.expected.contains("}") {
+        hints.push("a block started with '{' ends with '}'");
+    }
+
+    if rest.trim().is_empty() {
+        if before.trim_end().ends_with(';') {
+            hints.push("';' separates functions, drop the trailing one");
+        } else if expected.len() == 1 && expected[0] == "a space" {
+            hints.push("functions take at least one argument, e.g. 'p _v'");
+        }
+    }
+
+    return hints;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use parse::{script, jsonpath_script};
+
+    fn error(program: &str, jsonpath: bool) -> String {
+        let e = if jsonpath { jsonpath_script(program).err() } else { script(program).err() };
+        describe(program, &e.expect("a syntax error"), jsonpath)
+    }
+
+    fn hints(program: &str, jsonpath: bool) -> Vec<String> {
+        error(program, jsonpath).lines().filter(|l| l.starts_with("hint: ")).map(|l| String::from(&l[6..])).collect()
+    }
+
+    #[test]
+    fn message() {
+        assert_eq!(error(".a @ p \"x", false), "syntax error in program at column 10: unexpected end of program, expected the closing quote\n    .a @ p \"x\n             ^");
+        assert_eq!(error("$[1,\n  x] @ p _v", true), "syntax error in program at line 2, column 3: unexpected 'x', expected a number, a quoted string, '*', ':' or '?'\n      x] @ p _v\n      ^");
+    }
+
+    #[test]
+    fn expected_phrases() {
+        assert!(error(".a @ p .b = 1", false).contains("expected an arithmetic operator, a value, a name, a comparison, a logical operator, ';' and another function, '!', '(' or '.'\n"));
+        assert!(error(".a", false).contains("expected '@' or '%' mode separator, '->', '.', '..' or '?'\n"));
+        assert!(error(".a @ p", false).contains("unexpected end of program, expected a space\n"));
+        assert!(error("$[9007199254740992] @ p _v", true).contains("expected an integer from -9007199254740991 to 9007199254740991\n"));
+        assert!(error(".a @ p 1e999", false).contains("unexpected end of program, expected a number in the range of a double\n"));
+        assert!(error("$[?@ == -1e999] @ p _v", true).contains("expected a number in the range of a double\n"));
+    }
+
+    #[test]
+    fn hint() {
+        assert_eq!(hints("$.a @ p _v", false), ["jsonpath queries, which start with '$', need the -j option"]);
+        assert_eq!(hints(".a[0] @ p _v", false), ["index arrays with '.[0]' rather than '[0]', or use -j for jsonpath queries"]);
+        assert_eq!(hints(".a @ p .b = 1", false), ["values compare with '=='; a single '=' sets a variable, as in 'OFS = \",\"'"]);
+        assert_eq!(hints(".a @ null = 1", false), ["true, false and null are values, which cannot be set"]);
+        assert_eq!(hints(".a @ if .b { p _v", false), ["a block started with '{' ends with '}'"]);
+        assert_eq!(hints(".a @ p _v;", false), ["';' separates functions, drop the trailing one"]);
+        assert_eq!(hints(".a @ p", false), ["functions take at least one argument, e.g. 'p _v'"]);
+        assert_eq!(hints("a @ p _v", true), ["with -j the selector is a jsonpath query, which starts with '$'"]);
+        assert!(hints(".a", false)[0].starts_with("a program is '<selector> <mode> <action>'"));
+        assert!(hints(".a @ { p _v", false).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use std::error::Error;
+
+use json::Value;
+use json::Map;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use script::{JkError, syntax_error};
+
+
+// sequences and mappings may nest less deeply than this, as json arrays and
+// objects, so that converting them recursively does not overflow the stack
+const MAX_DEPTH: usize = 128;
+
+// the documents of a yaml stream, as json values. mapping keys become
+// strings, as json requires; keys that are not scalars are an error.
+pub fn documents(input: &str) -> Result<Vec<Value>, JkError>
+{
+    let documents = try!(YamlLoader::load_from_str(input).map_err(|e| {
+        #[allow(deprecated)]
+        let msg = String::from(e.description());
+        syntax_error(msg, e.marker().line(), e.marker().col() + 1)
+    }));
+
+    let mut values = Vec::new();
+
+    for document in documents {
+        values.push(try!(to_value(document, 0)));
+    }
+
+    return Ok(values);
+}
+
+// `depth` sequences and mappings are open around `yaml`
+fn to_value(yaml: Yaml, depth: usize) -> Result<Value, JkError>
+{
+    if (yaml.is_array() || yaml.is_hash()) && depth + 1 == MAX_DEPTH {
+        return Err(yaml_error(String::from("recursion limit exceeded")));
+    }
+
+    match yaml {
+        Yaml::Null => Ok(Value::Null),
+        Yaml::Boolean(b) => Ok(Value::Bool(b)),
+        Yaml::Integer(i) => Ok(Value::from(i)),
+        // .inf and .nan, which json has no numbers for, become null
+        Yaml::Real(ref real) => {
+            match yaml.as_f64() {
+                Some(f) => Ok(Value::from(f)),
+                None => Err(yaml_error(format!("invalid number {}", real))),
+            }
+        },
+        Yaml::String(s) => Ok(Value::String(s)),
+        Yaml::Array(vector) => {
+            let mut result = Vec::new();
+            for v in vector {
+                result.push(try!(to_value(v, depth + 1)));
+            }
+            Ok(Value::Array(result))
+        },
+        Yaml::Hash(hash) => {
+            let mut result = Map::new();
+            for (k, v) in hash {
+                result.insert(try!(key_string(k)), try!(to_value(v, depth + 1)));
+            }
+            Ok(Value::Object(result))
+        },
+        Yaml::Alias(_) | Yaml::BadValue => Err(yaml_error(String::from("unresolved alias"))),
+    }
+}
+
+fn key_string(key: Yaml) -> Result<String, JkError>
+{
+    match key {
+        Yaml::String(s) | Yaml::Real(s) => Ok(s),
+        Yaml::Integer(i) => Ok(i.to_string()),
+        Yaml::Boolean(b) => Ok(b.to_string()),
+        Yaml::Null => Ok(String::from("null")),
+        _ => Err(yaml_error(String::from("mapping keys must be scalars"))),
+    }
+}
+
+fn yaml_error(msg: String) -> JkError
+{
+    syntax_error(msg, 0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> String {
+        (0..depth).map(|i| format!("{}-\n", "  ".repeat(i))).collect()
+    }
+
+    #[test]
+    fn nesting_limit() {
+        assert_eq!(documents(&nested(127)).unwrap().len(), 1);
+        match documents(&nested(128)) {
+            Err(JkError::Parse(e)) => assert_eq!(e.msg, "recursion limit exceeded"),
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+}